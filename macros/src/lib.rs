@@ -0,0 +1,211 @@
+//! Procedural macros for [path-slash](https://crates.io/crates/path-slash) crate.
+//!
+//! Do not depend on this crate directly. Enable `macros` feature of `path-slash` crate and use
+//! the macros re-exported from it.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Convert the slash path literal into `&'static Path` at compile time.
+///
+/// Any '/' in the slash path is replaced with the file path separator of the target. The result
+/// is the same as [`PathBufExt::from_slash`](https://docs.rs/path-slash/latest/path_slash/trait.PathBufExt.html#tymethod.from_slash)
+/// but no heap allocation happens at runtime.
+///
+/// ```
+/// # use std::path::{Path, PathBuf};
+/// use path_slash::slash_path;
+/// use path_slash::PathBufExt as _;
+///
+/// let p: &'static Path = slash_path!("fixtures/a/b.txt");
+/// assert_eq!(p, PathBuf::from_slash("fixtures/a/b.txt"));
+///
+/// #[cfg(target_os = "windows")]
+/// assert_eq!(p, Path::new(r"fixtures\a\b.txt"));
+///
+/// #[cfg(not(target_os = "windows"))]
+/// assert_eq!(p, Path::new("fixtures/a/b.txt"));
+/// ```
+#[proc_macro]
+pub fn slash_path(input: TokenStream) -> TokenStream {
+    let lits = match parse_literals(input) {
+        Ok(lits) => lits,
+        Err(err) => return err,
+    };
+    if lits.len() != 1 {
+        return compile_error(
+            "slash_path! takes exactly one string literal",
+            Span::call_site(),
+        );
+    }
+    expand(&lits[0])
+}
+
+/// Concatenate the slash path literals and convert the result into `&'static Path` at compile
+/// time.
+///
+/// The literals are concatenated as they are like [`concat!`]. No separator is inserted between
+/// them. The conversion is the same as [`slash_path!`].
+///
+/// ```
+/// # use std::path::{Path, PathBuf};
+/// use path_slash::concat_slash_path;
+/// use path_slash::PathBufExt as _;
+///
+/// let p = concat_slash_path!("fixtures/", "a/b", ".txt");
+/// assert_eq!(p, PathBuf::from_slash("fixtures/a/b.txt"));
+/// ```
+#[proc_macro]
+pub fn concat_slash_path(input: TokenStream) -> TokenStream {
+    let lits = match parse_literals(input) {
+        Ok(lits) => lits,
+        Err(err) => return err,
+    };
+    if lits.is_empty() {
+        return compile_error(
+            "concat_slash_path! takes at least one string literal",
+            Span::call_site(),
+        );
+    }
+    expand(&lits.concat())
+}
+
+fn expand(slash: &str) -> TokenStream {
+    // Choose the separator by the target where the macro is expanded, not by the host where the
+    // macro is compiled, so that cross compilation works
+    let windows = slash.replace('/', "\\");
+    let src = format!(
+        "{{ \
+            #[cfg(target_os = \"windows\")] const PATH: &str = {}; \
+            #[cfg(not(target_os = \"windows\"))] const PATH: &str = {}; \
+            ::std::path::Path::new(PATH) \
+        }}",
+        Literal::string(&windows),
+        Literal::string(slash),
+    );
+    src.parse().unwrap()
+}
+
+// Parse comma-separated string literals. A trailing comma is allowed
+fn parse_literals(input: TokenStream) -> Result<Vec<String>, TokenStream> {
+    let mut lits = vec![];
+    let mut expect_comma = false;
+
+    for tok in input {
+        match tok {
+            TokenTree::Punct(ref p) if expect_comma && p.as_char() == ',' => expect_comma = false,
+            _ if expect_comma => return Err(compile_error("expected `,`", tok.span())),
+            _ => {
+                lits.push(
+                    parse_literal(&tok)
+                        .ok_or_else(|| compile_error("expected string literal", tok.span()))?,
+                );
+                expect_comma = true;
+            }
+        }
+    }
+
+    Ok(lits)
+}
+
+fn parse_literal(tok: &TokenTree) -> Option<String> {
+    match tok {
+        TokenTree::Literal(lit) => unescape(&lit.to_string()),
+        // Literals passed through `macro_rules!` as `$e:expr` are wrapped in invisible groups
+        TokenTree::Group(g) if g.delimiter() == Delimiter::None => {
+            let mut toks = g.stream().into_iter();
+            match (toks.next(), toks.next()) {
+                (Some(tok), None) => parse_literal(&tok),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Get the content of the string literal from its source representation. Returns `None` when it
+// is not a string literal such as byte strings, characters or numbers
+fn unescape(repr: &str) -> Option<String> {
+    if let Some(raw) = repr.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = &raw[hashes..];
+        if raw.len() < 2 || !raw.starts_with('"') {
+            return None;
+        }
+        let body = &raw[1..];
+        let end = body.rfind('"')?;
+        if body[end + 1..].len() != hashes || !body[end + 1..].chars().all(|c| c == '#') {
+            return None;
+        }
+        return Some(body[..end].to_string());
+    }
+
+    if repr.len() < 2 || !repr.starts_with('"') || !repr.ends_with('"') {
+        return None;
+    }
+
+    let body = &repr[1..repr.len() - 1];
+    let mut out = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            'r' => out.push('\r'),
+            't' => out.push('\t'),
+            '0' => out.push('\0'),
+            c @ '\\' | c @ '\'' | c @ '"' => out.push(c),
+            'x' => {
+                let hi = chars.next()?.to_digit(8)?;
+                let lo = chars.next()?.to_digit(16)?;
+                out.push(char::from((hi * 16 + lo) as u8));
+            }
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let mut value = 0;
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        '_' => {}
+                        c => value = value * 16 + c.to_digit(16)?,
+                    }
+                }
+                out.push(std::char::from_u32(value)?);
+            }
+            // Line continuation skips the newline and the following whitespaces
+            '\n' | '\r' => {
+                while let Some(&c) = chars.peek() {
+                    if !c.is_whitespace() {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(out)
+}
+
+fn compile_error(msg: &str, span: Span) -> TokenStream {
+    let mut lit = Literal::string(msg);
+    lit.set_span(span);
+    let mut group = Group::new(Delimiter::Parenthesis, TokenTree::from(lit).into());
+    group.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+
+    let toks: Vec<TokenTree> = vec![
+        Ident::new("compile_error", span).into(),
+        bang.into(),
+        group.into(),
+    ];
+    toks.into_iter().collect()
+}
@@ -0,0 +1,247 @@
+//! Comparison of slash paths which considers how file systems treat file names.
+//!
+//! File systems on Windows and macOS are case-insensitive by default, so `Foo/Bar.TXT` and
+//! `foo/bar.txt` point to the same file. macOS also stores file names in Unicode NFD. Comparing
+//! slash paths as plain strings reports false mismatches for such paths.
+//!
+//! The types implementing [`Equivalence`] define how slash paths are compared and hashed.
+//!
+//! - [`CaseFoldEq`]: Compare paths case-insensitively
+//! - [`NfcEq`]: Compare paths after normalizing them into Unicode NFC. This is available when
+//!   the `unicode-normalization` feature is enabled
+//! - `(A, B)`: Apply both equivalences. For example, `(CaseFoldEq, NfcEq)` is suitable for macOS
+//!
+//! [`SlashPathKey`] wraps a slash path with an equivalence so that it can be used as a key of
+//! [`HashMap`](std::collections::HashMap) or [`HashSet`](std::collections::HashSet).
+//!
+//! ```
+//! use path_slash::compare::{CaseFoldEq, Equivalence, SlashPathKey};
+//! use std::collections::HashSet;
+//!
+//! assert!(CaseFoldEq::equivalent("Foo/Bar.TXT", "foo/bar.txt"));
+//!
+//! let mut set = HashSet::new();
+//! set.insert(SlashPathKey::<CaseFoldEq>::new("Foo/Bar.TXT"));
+//! assert!(!set.insert(SlashPathKey::new("foo/bar.txt")));
+//! ```
+//!
+//! Paths are compared as strings. They are not cleaned, so `foo/bar` and `foo//bar` are still
+//! different. Use [`SlashPath::clean`](crate::SlashPath::clean) before comparing them if needed.
+
+use crate::{SlashPath, SlashPathBuf};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// An equivalence relation of slash paths.
+///
+/// Two slash paths are equivalent when their normalized forms returned from
+/// [`Equivalence::normalize`] are equal. [`Equivalence::equivalent`] and
+/// [`Equivalence::hash_path`] are consistent with each other.
+pub trait Equivalence {
+    /// Normalize the slash path. Only when the path is changed, heap allocation happens and
+    /// `Cow::Owned` is returned.
+    fn normalize(path: &str) -> Cow<'_, str>;
+
+    /// Return `true` when the two slash paths are equivalent.
+    fn equivalent(lhs: &str, rhs: &str) -> bool {
+        Self::normalize(lhs) == Self::normalize(rhs)
+    }
+
+    /// Feed the normalized form of the slash path into the hasher.
+    fn hash_path<H: Hasher>(path: &str, state: &mut H) {
+        Self::normalize(path).hash(state);
+    }
+}
+
+/// Compare slash paths case-insensitively.
+///
+/// Paths are compared with the simple case folding of Unicode, which maps each character to
+/// one character regardless of its position. So non-ASCII characters such as `Ä` and `ä`, `ſ`
+/// and `s`, or final `ς` and `σ` are also equivalent. Since `ß` is not folded into `ss` by the
+/// simple case folding, `ß` and `SS` are not equivalent as on NTFS. The case folding follows the
+/// Unicode version of the standard library.
+///
+/// ```
+/// use path_slash::compare::{CaseFoldEq, Equivalence};
+///
+/// assert!(CaseFoldEq::equivalent("C:/Users/Foo", "c:/users/foo"));
+/// assert!(CaseFoldEq::equivalent("Ä.txt", "ä.TXT"));
+/// assert!(!CaseFoldEq::equivalent("Straße", "STRASSE"));
+/// assert_eq!(CaseFoldEq::normalize("Foo/Bar"), "foo/bar");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CaseFoldEq;
+
+fn single_char<I: Iterator<Item = char>>(mut chars: I) -> Option<char> {
+    let c = chars.next()?;
+    if chars.next().is_none() {
+        Some(c)
+    } else {
+        None
+    }
+}
+
+// The simple case folding (the `C` and `S` mappings of CaseFolding.txt) is equal to lowercasing
+// the uppercase of the character when both mappings are one-to-one, except for a few characters
+fn case_fold(c: char) -> char {
+    if c.is_ascii() {
+        return c.to_ascii_lowercase();
+    }
+    match c {
+        '\u{131}' => return c, // Dotless `ı` is not folded into `i`
+        '\u{1fd3}' => return '\u{390}',
+        '\u{1fe3}' => return '\u{3b0}',
+        '\u{fb05}' => return '\u{fb06}',
+        _ => {}
+    }
+    let upper = single_char(c.to_uppercase()).unwrap_or(c);
+    single_char(upper.to_lowercase()).unwrap_or(upper)
+}
+
+impl Equivalence for CaseFoldEq {
+    fn normalize(path: &str) -> Cow<'_, str> {
+        if path.chars().all(|c| case_fold(c) == c) {
+            Cow::Borrowed(path)
+        } else {
+            Cow::Owned(path.chars().map(case_fold).collect())
+        }
+    }
+}
+
+/// Compare slash paths after normalizing them into Unicode NFC.
+///
+/// File names created on macOS are stored in NFD. For example, `é` is stored as `e` followed by
+/// U+0301. With this equivalence they are equal to file names in NFC created on other OSes.
+///
+/// This type is available when the `unicode-normalization` feature is enabled.
+///
+/// ```
+/// use path_slash::compare::{Equivalence, NfcEq};
+///
+/// assert!(NfcEq::equivalent("caf\u{e9}/a.txt", "cafe\u{301}/a.txt"));
+/// assert!(!NfcEq::equivalent("Caf\u{e9}", "caf\u{e9}"));
+/// ```
+#[cfg(feature = "unicode-normalization")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NfcEq;
+
+#[cfg(feature = "unicode-normalization")]
+impl Equivalence for NfcEq {
+    fn normalize(path: &str) -> Cow<'_, str> {
+        use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization as _};
+
+        match is_nfc_quick(path.chars()) {
+            IsNormalized::Yes => Cow::Borrowed(path),
+            _ => Cow::Owned(path.nfc().collect()),
+        }
+    }
+}
+
+impl<A: Equivalence, B: Equivalence> Equivalence for (A, B) {
+    fn normalize(path: &str) -> Cow<'_, str> {
+        match A::normalize(path) {
+            Cow::Borrowed(path) => B::normalize(path),
+            Cow::Owned(path) => Cow::Owned(B::normalize(&path).into_owned()),
+        }
+    }
+}
+
+/// A slash path which is compared and hashed with the equivalence `E`.
+///
+/// This is useful as a key of [`HashMap`](std::collections::HashMap) or
+/// [`HashSet`](std::collections::HashSet) to deduplicate slash paths which point to the same
+/// file. The normalized form is computed once on construction. The original path is kept and
+/// returned from [`SlashPathKey::as_slash_path`].
+///
+/// ```
+/// use path_slash::compare::{CaseFoldEq, SlashPathKey};
+/// use std::collections::HashMap;
+///
+/// let mut sizes = HashMap::new();
+/// sizes.insert(SlashPathKey::<CaseFoldEq>::new("src/Lib.rs"), 42);
+///
+/// let key = SlashPathKey::new("SRC/lib.RS");
+/// assert_eq!(sizes.get(&key), Some(&42));
+/// assert_eq!(key.as_slash_path(), "SRC/lib.RS");
+/// ```
+pub struct SlashPathKey<E> {
+    path: SlashPathBuf,
+    normalized: String,
+    _eq: PhantomData<E>,
+}
+
+impl<E: Equivalence> SlashPathKey<E> {
+    /// Create a new key from the slash path.
+    pub fn new<P: Into<SlashPathBuf>>(path: P) -> Self {
+        let path = path.into();
+        let normalized = E::normalize(path.as_str()).into_owned();
+        Self {
+            path,
+            normalized,
+            _eq: PhantomData,
+        }
+    }
+
+    /// Return the original slash path.
+    pub fn as_slash_path(&self) -> &SlashPath {
+        self.path.as_slash_path()
+    }
+
+    /// Return the normalized form of the slash path which is used for comparison.
+    pub fn normalized(&self) -> &str {
+        &self.normalized
+    }
+
+    /// Convert the key into the original slash path.
+    pub fn into_slash_path_buf(self) -> SlashPathBuf {
+        self.path
+    }
+}
+
+impl<E> Clone for SlashPathKey<E> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            normalized: self.normalized.clone(),
+            _eq: PhantomData,
+        }
+    }
+}
+
+impl<E> fmt::Debug for SlashPathKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlashPathKey")
+            .field("path", &self.path)
+            .field("normalized", &self.normalized)
+            .finish()
+    }
+}
+
+impl<E> fmt::Display for SlashPathKey<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.path, f)
+    }
+}
+
+impl<E> PartialEq for SlashPathKey<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized == other.normalized
+    }
+}
+
+impl<E> Eq for SlashPathKey<E> {}
+
+impl<E> Hash for SlashPathKey<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized.hash(state);
+    }
+}
+
+impl<E> AsRef<str> for SlashPathKey<E> {
+    fn as_ref(&self) -> &str {
+        self.path.as_str()
+    }
+}
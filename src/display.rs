@@ -0,0 +1,105 @@
+#[cfg(target_os = "windows")]
+use crate::windows;
+use crate::PathExt as _;
+use std::fmt;
+use std::path::Path;
+
+/// Helper to print a file path as slash path without heap allocation. This is created by
+/// [`PathExt::display_slash`](crate::PathExt::display_slash).
+///
+/// The output is the same as [`PathExt::to_slash_lossy`](crate::PathExt::to_slash_lossy). With
+/// `{:?}`, the output is quoted and escaped in the same way as `str`. Note that formatting with
+/// `{:?}` may allocate the slash path on Windows.
+///
+/// ```
+/// # use std::path::Path;
+/// use path_slash::PathExt as _;
+///
+/// #[cfg(target_os = "windows")]
+/// let p = Path::new(r"foo\bar\piyo.txt");
+///
+/// #[cfg(not(target_os = "windows"))]
+/// let p = Path::new("foo/bar/piyo.txt");
+///
+/// assert_eq!(format!("{}", p.display_slash()), "foo/bar/piyo.txt");
+/// assert_eq!(format!("{:?}", p.display_slash()), r#""foo/bar/piyo.txt""#);
+/// ```
+#[derive(Clone, Copy)]
+pub struct DisplaySlash<'a> {
+    path: &'a Path,
+}
+
+impl<'a> DisplaySlash<'a> {
+    pub(crate) fn new(path: &'a Path) -> Self {
+        Self { path }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        // `Path::display` replaces non-Unicode sequences with U+FFFD as `to_string_lossy` does
+        write!(w, "{}", self.path.display())
+    }
+
+    // Keep this consistent with `to_slash_lossy` on Windows
+    #[cfg(target_os = "windows")]
+    fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        use std::path::Component;
+
+        let mut comps = self.path.components();
+        let root_only = comps.next() == Some(Component::RootDir) && comps.next().is_none();
+
+        // '/' is written lazily since the last one is removed unless the path ends with '\'
+        let mut needs_sep = false;
+        for c in self.path.components() {
+            if needs_sep {
+                w.write_char('/')?;
+            }
+            match c {
+                Component::RootDir => { /* empty */ }
+                Component::CurDir => w.write_char('.')?,
+                Component::ParentDir => w.write_str("..")?,
+                Component::Prefix(prefix) => {
+                    write!(w, "{}", Path::new(prefix.as_os_str()).display())?;
+                    // C:\foo is [Prefix, RootDir, Normal]. Avoid C://
+                    needs_sep = false;
+                    continue;
+                }
+                Component::Normal(s) => write!(w, "{}", Path::new(s).display())?,
+            }
+            needs_sep = true;
+        }
+
+        if needs_sep && (root_only || windows::ends_with_main_sep(self.path)) {
+            w.write_char('/')?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for DisplaySlash<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+impl<'a> fmt::Debug for DisplaySlash<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Delegate to `str` so that the escaping always matches the standard library
+        fmt::Debug::fmt(&*self.path.to_slash_lossy(), f)
+    }
+}
+
+/// An error returned when a file path cannot be converted into UTF-8 slash path because it
+/// contains non-Unicode sequences. This is returned by
+/// [`PathExt::write_slash`](crate::PathExt::write_slash).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonUtf8;
+
+impl fmt::Display for NonUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("path contains non-Unicode sequence")
+    }
+}
+
+impl std::error::Error for NonUtf8 {}
@@ -0,0 +1,231 @@
+//! Conversion of backslash paths which contain escape sequences.
+//!
+//! [`PathBufExt::from_backslash`](crate::PathBufExt::from_backslash) replaces every '\\' with a
+//! separator. But paths taken from shell commands, Makefiles or C string literals use '\\' also
+//! for escaping characters like `foo\ bar`. [`BackslashConverter`] tells separator backslashes
+//! apart from escape backslashes following the rules of [`EscapeStyle`], and keeps the escaped
+//! characters in the result.
+//!
+//! ```
+//! use path_slash::escape::{BackslashConverter, EscapeStyle};
+//!
+//! let shell = BackslashConverter::new(EscapeStyle::Shell);
+//! assert_eq!(shell.to_slash(r"My\ Documents\foo\(1\).txt"), "My Documents/foo(1).txt");
+//!
+//! let make = BackslashConverter::new(EscapeStyle::Make);
+//! assert_eq!(make.to_slash(r"src\100\%\ done.c"), "src/100% done.c");
+//!
+//! let c = BackslashConverter::new(EscapeStyle::CString);
+//! assert_eq!(c.to_slash(r"C:\\Users\\\x41\x42"), "C:/Users/AB");
+//! ```
+
+#[cfg(feature = "std")]
+use crate::PathBufExt as _;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+/// The rules to tell escape backslashes from separator backslashes.
+///
+/// In every style, `\\` is one separator since the escaped character is a backslash. A
+/// backslash which does not start an escape sequence is a separator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EscapeStyle {
+    /// POSIX shell words. A backslash followed by a space, a tab or one of
+    /// `` '"$`&|;<>()*?[]#~=%!{} `` escapes the character.
+    Shell,
+    /// Makefile targets and prerequisites. A backslash followed by a space, a tab, `#`, `%` or
+    /// `:` escapes the character.
+    Make,
+    /// C string literals. Every backslash starts an escape sequence such as `\n`, `\"`, `\x41`
+    /// or `\101`, and the sequence is decoded. Unknown escape sequences are treated as a
+    /// separator followed by the character.
+    CString,
+}
+
+impl EscapeStyle {
+    fn is_escapable(self, b: u8) -> bool {
+        match self {
+            EscapeStyle::Shell => match b {
+                b' ' | b'\t' | b'\'' | b'"' | b'$' | b'`' | b'&' | b'|' | b';' => true,
+                b'<' | b'>' | b'(' | b')' | b'*' | b'?' | b'[' | b']' | b'{' | b'}' => true,
+                b'#' | b'~' | b'=' | b'%' | b'!' => true,
+                _ => false,
+            },
+            EscapeStyle::Make => match b {
+                b' ' | b'\t' | b'#' | b'%' | b':' => true,
+                _ => false,
+            },
+            EscapeStyle::CString => false,
+        }
+    }
+}
+
+fn octal_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'7' => Some(b - b'0'),
+        _ => None,
+    }
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+// Decode the escape sequence of C string literal at the start of `s` which follows a backslash.
+// Returns the decoded byte and the length of the sequence.
+fn decode_c_escape(s: &[u8]) -> Option<(u8, usize)> {
+    let first = *s.first()?;
+    let simple = match first {
+        b'n' => Some(b'\n'),
+        b't' => Some(b'\t'),
+        b'r' => Some(b'\r'),
+        b'a' => Some(0x07),
+        b'b' => Some(0x08),
+        b'f' => Some(0x0c),
+        b'v' => Some(0x0b),
+        b'"' | b'\'' | b'?' => Some(first),
+        _ => None,
+    };
+    if let Some(b) = simple {
+        return Some((b, 1));
+    }
+
+    if first == b'x' {
+        let mut value = 0u8;
+        let mut len = 1;
+        while len < 3 {
+            match s.get(len).cloned().and_then(hex_value) {
+                Some(d) => value = value << 4 | d,
+                None => break,
+            }
+            len += 1;
+        }
+        return if len > 1 { Some((value, len)) } else { None };
+    }
+
+    let mut value = 0u16;
+    let mut len = 0;
+    while len < 3 {
+        match s.get(len).cloned().and_then(octal_value) {
+            Some(d) => value = value << 3 | u16::from(d),
+            None => break,
+        }
+        len += 1;
+    }
+    if len > 0 && value <= 0xff {
+        Some((value as u8, len))
+    } else {
+        None
+    }
+}
+
+/// A converter from backslash paths to slash paths which keeps escaped characters.
+///
+/// See [the module document](self) for the details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackslashConverter {
+    style: EscapeStyle,
+}
+
+impl BackslashConverter {
+    /// Create a new converter which follows the escaping rules of the style.
+    pub fn new(style: EscapeStyle) -> Self {
+        Self { style }
+    }
+
+    /// Return the escaping rules of the converter.
+    pub fn style(&self) -> EscapeStyle {
+        self.style
+    }
+
+    /// Convert the backslash path into a slash path.
+    ///
+    /// Separator backslashes are replaced with '/'. Escape backslashes are removed and the
+    /// escaped characters are kept as they are. Other characters including '/' are not changed.
+    /// Only when the path contains '\\', heap allocation happens and `Cow::Owned` is returned.
+    ///
+    /// When decoded `\x` or octal escape sequences in [`EscapeStyle::CString`] are not valid
+    /// UTF-8, they are replaced with U+FFFD.
+    ///
+    /// ```
+    /// use path_slash::escape::{BackslashConverter, EscapeStyle};
+    ///
+    /// let conv = BackslashConverter::new(EscapeStyle::Shell);
+    /// assert_eq!(conv.to_slash(r"foo\bar\ baz"), "foo/bar baz");
+    /// assert_eq!(conv.to_slash(r"foo\\bar"), "foo/bar");
+    /// assert_eq!(conv.to_slash("foo/bar"), "foo/bar");
+    /// ```
+    pub fn to_slash<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        if !path.contains('\\') {
+            return Cow::Borrowed(path);
+        }
+
+        let bytes = path.as_bytes();
+        let mut buf = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+            i += 1;
+            if b != b'\\' {
+                buf.push(b);
+                continue;
+            }
+
+            match bytes.get(i) {
+                Some(b'\\') => {
+                    buf.push(b'/');
+                    i += 1;
+                }
+                Some(&c) if self.style.is_escapable(c) => {
+                    buf.push(c);
+                    i += 1;
+                }
+                Some(_) if self.style == EscapeStyle::CString => {
+                    match decode_c_escape(&bytes[i..]) {
+                        Some((c, len)) => {
+                            buf.push(c);
+                            i += len;
+                        }
+                        None => buf.push(b'/'),
+                    }
+                }
+                _ => buf.push(b'/'),
+            }
+        }
+
+        match String::from_utf8(buf) {
+            Ok(s) => Cow::Owned(s),
+            Err(err) => Cow::Owned(String::from_utf8_lossy(err.as_bytes()).into_owned()),
+        }
+    }
+
+    /// Convert the backslash path into [`PathBuf`]. This is the same as
+    /// [`BackslashConverter::to_slash`] followed by
+    /// [`PathBufExt::from_slash`](crate::PathBufExt::from_slash).
+    ///
+    /// ```
+    /// # use std::path::PathBuf;
+    /// use path_slash::escape::{BackslashConverter, EscapeStyle};
+    ///
+    /// let conv = BackslashConverter::new(EscapeStyle::Make);
+    /// let p = conv.to_path_buf(r"foo\bar\ baz.o");
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// assert_eq!(p, PathBuf::from(r"foo\bar baz.o"));
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// assert_eq!(p, PathBuf::from("foo/bar baz.o"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_path_buf(&self, path: &str) -> PathBuf {
+        PathBuf::from_slash(self.to_slash(path))
+    }
+}
@@ -0,0 +1,494 @@
+//! Glob pattern matching over slash paths.
+//!
+//! [`SlashGlob`] is a compiled glob pattern. Paths are always matched as slash paths. A [`Path`]
+//! is converted with [`PathExt::to_slash_lossy`](crate::PathExt::to_slash_lossy) before
+//! matching, so the same pattern behaves in the same way on every OS.
+//!
+//! | Syntax    | Meaning                                                                    |
+//! |-----------|----------------------------------------------------------------------------|
+//! | `?`       | Any single character except for `/`                                        |
+//! | `*`       | Zero or more characters except for `/`                                     |
+//! | `**`      | Zero or more path components. It must be a whole component like `a/**/b`   |
+//! | `[abc]`   | Any character in the brackets. `[a-z]` matches a range of characters       |
+//! | `[!abc]`  | Any character not in the brackets. `[^abc]` is the same                    |
+//! | `{a,b}`   | Either `a` or `b`. Alternatives can contain any other syntax               |
+//! | `!`       | Negate the whole pattern when it is at the start of the pattern            |
+//! | `\`       | Escape the next character                                                  |
+//!
+//! `/` is never matched by `?`, `*` and `[...]`.
+//!
+//! ```
+//! use path_slash::glob::SlashGlob;
+//!
+//! let glob = SlashGlob::new("src/**/*.{rs,toml}").unwrap();
+//! assert!(glob.is_match("src/lib.rs"));
+//! assert!(glob.is_match("src/foo/bar/Cargo.toml"));
+//! assert!(!glob.is_match("tests/lib.rs"));
+//!
+//! let glob = SlashGlob::new("!*.txt").unwrap();
+//! assert!(glob.is_match("foo.rs"));
+//! assert!(!glob.is_match("foo.txt"));
+//! ```
+
+#[cfg(feature = "std")]
+use crate::PathExt as _;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// The kind of [`GlobError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlobErrorKind {
+    /// `[` is not closed with `]`.
+    UnclosedClass,
+    /// The range in `[...]` is reversed like `[z-a]`.
+    InvalidRange,
+    /// `{` is not closed with `}`.
+    UnclosedBrace,
+    /// `**` is not a whole path component like `a**` or `**b`.
+    InvalidRecursive,
+    /// The pattern ends with `\`.
+    DanglingEscape,
+}
+
+impl fmt::Display for GlobErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            GlobErrorKind::UnclosedClass => "unclosed character class",
+            GlobErrorKind::InvalidRange => "invalid range in character class",
+            GlobErrorKind::UnclosedBrace => "unclosed alternatives",
+            GlobErrorKind::InvalidRecursive => "`**` must be a whole path component",
+            GlobErrorKind::DanglingEscape => "dangling escape",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// An error returned when a glob pattern is invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlobError {
+    pattern: String,
+    pos: usize,
+    kind: GlobErrorKind,
+}
+
+impl GlobError {
+    /// The kind of the error.
+    pub fn kind(&self) -> GlobErrorKind {
+        self.kind
+    }
+
+    /// The byte offset in the pattern where the error occurred.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The invalid glob pattern.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid glob pattern {:?}: {} at byte offset {}",
+            self.pattern, self.kind, self.pos,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GlobError {}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Char(char),
+    AnyChar,
+    Star,
+    // `**/` matches zero or more path components including the trailing '/'
+    RecursiveDirs,
+    // `**` at the end of pattern matches anything
+    RecursiveAll,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+    Alt(Vec<Vec<Node>>),
+}
+
+impl Node {
+    fn matches_class(negated: bool, ranges: &[(char, char)], c: char) -> bool {
+        c != '/' && ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != negated
+    }
+}
+
+// Options to parse glob patterns. Ignore files such as .gitignore use the glob syntax without
+// brace expansion and negation, and treat `**` which is not a whole path component as `*`.
+#[derive(Clone, Copy)]
+pub(crate) struct Options {
+    pub(crate) braces: bool,
+    pub(crate) negation: bool,
+    pub(crate) strict_recursive: bool,
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    chars: Vec<(usize, char)>,
+    idx: usize,
+    opts: Options,
+}
+
+// Replace `**` at the end of the sequence with `*`. Returns whether it was replaced
+fn demote_recursive_all(nodes: &mut [Node]) -> bool {
+    match nodes.last_mut() {
+        Some(node) => match node {
+            Node::RecursiveAll => {
+                *node = Node::Star;
+                true
+            }
+            Node::Alt(alts) => {
+                // Do not short-circuit since all alternatives need to be replaced
+                let mut found = false;
+                for alt in alts {
+                    found |= demote_recursive_all(alt);
+                }
+                found
+            }
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, pos: usize, kind: GlobErrorKind) -> GlobError {
+        GlobError {
+            pattern: self.pattern.to_string(),
+            pos,
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.idx).map(|&(_, c)| c)
+    }
+
+    fn is_seq_end(&self, in_brace: bool) -> bool {
+        match self.peek() {
+            None => true,
+            Some(',') | Some('}') => in_brace,
+            Some(_) => false,
+        }
+    }
+
+    // `comp_start` tells whether the sequence starts at the start of a path component. An
+    // alternative in braces inherits it from the enclosing sequence
+    fn parse_seq(&mut self, in_brace: bool, mut comp_start: bool) -> Result<Vec<Node>, GlobError> {
+        let mut nodes = vec![];
+        while let Some(&(pos, c)) = self.chars.get(self.idx) {
+            if self.is_seq_end(in_brace) {
+                break;
+            }
+            self.idx += 1;
+            let node = match c {
+                '{' if self.opts.braces => {
+                    let mut alts = vec![];
+                    loop {
+                        alts.push(self.parse_seq(true, comp_start)?);
+                        match self.peek() {
+                            Some(',') => self.idx += 1,
+                            Some('}') => {
+                                self.idx += 1;
+                                break;
+                            }
+                            _ => return Err(self.error(pos, GlobErrorKind::UnclosedBrace)),
+                        }
+                    }
+                    // `**` at the end of an alternative is not a whole component when something
+                    // other than `/` follows the braces like `{**}x`
+                    if !self.is_seq_end(in_brace) && self.peek() != Some('/') {
+                        for alt in &mut alts {
+                            if demote_recursive_all(alt) && self.opts.strict_recursive {
+                                return Err(self.error(pos, GlobErrorKind::InvalidRecursive));
+                            }
+                        }
+                    }
+                    Node::Alt(alts)
+                }
+                '*' if self.peek() == Some('*') => {
+                    self.idx += 1;
+                    if comp_start && self.is_seq_end(in_brace) {
+                        Node::RecursiveAll
+                    } else if comp_start && self.peek() == Some('/') {
+                        self.idx += 1;
+                        nodes.push(Node::RecursiveDirs);
+                        continue; // Still at the start of a component
+                    } else if self.opts.strict_recursive {
+                        return Err(self.error(pos, GlobErrorKind::InvalidRecursive));
+                    } else {
+                        Node::Star
+                    }
+                }
+                '*' => Node::Star,
+                '?' => Node::AnyChar,
+                '[' => self.parse_class(pos)?,
+                '\\' => match self.peek() {
+                    Some(c) => {
+                        self.idx += 1;
+                        comp_start = false;
+                        nodes.push(Node::Char(c));
+                        continue;
+                    }
+                    None => return Err(self.error(pos, GlobErrorKind::DanglingEscape)),
+                },
+                c => Node::Char(c),
+            };
+            comp_start = c == '/';
+            nodes.push(node);
+        }
+        Ok(nodes)
+    }
+
+    fn parse_class_char(&mut self, start: usize) -> Result<char, GlobError> {
+        match self.peek() {
+            Some('\\') => {
+                self.idx += 1;
+                let c = self.peek();
+                self.idx += 1;
+                c.ok_or_else(|| self.error(start, GlobErrorKind::UnclosedClass))
+            }
+            Some(c) => {
+                self.idx += 1;
+                Ok(c)
+            }
+            None => Err(self.error(start, GlobErrorKind::UnclosedClass)),
+        }
+    }
+
+    fn parse_class(&mut self, start: usize) -> Result<Node, GlobError> {
+        let negated = match self.peek() {
+            Some('!') | Some('^') => {
+                self.idx += 1;
+                true
+            }
+            _ => false,
+        };
+
+        let mut ranges = vec![];
+        loop {
+            // `]` just after `[` or `[!` is a literal character
+            if self.peek() == Some(']') && !ranges.is_empty() {
+                self.idx += 1;
+                break;
+            }
+            let pos = self.chars.get(self.idx).map_or(start, |&(p, _)| p);
+            let lo = self.parse_class_char(start)?;
+            let is_range = self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.idx + 1)
+                    .map_or(false, |&(_, c)| c != ']');
+            if is_range {
+                self.idx += 1;
+                let hi = self.parse_class_char(start)?;
+                if lo > hi {
+                    return Err(self.error(pos, GlobErrorKind::InvalidRange));
+                }
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+
+        Ok(Node::Class { negated, ranges })
+    }
+}
+
+// Compute the set of positions in `text` which are reachable after matching `nodes` from the
+// positions in `cur`. This simulates all possible matches at once so it never backtracks.
+fn step(nodes: &[Node], text: &[char], mut cur: Vec<bool>) -> Vec<bool> {
+    let len = text.len();
+    for node in nodes {
+        if !cur.contains(&true) {
+            break;
+        }
+        let mut next = vec![false; len + 1];
+        match node {
+            Node::Char(c) => {
+                for i in 0..len {
+                    next[i + 1] = cur[i] && text[i] == *c;
+                }
+            }
+            Node::AnyChar => {
+                for i in 0..len {
+                    next[i + 1] = cur[i] && text[i] != '/';
+                }
+            }
+            Node::Class { negated, ranges } => {
+                for i in 0..len {
+                    next[i + 1] = cur[i] && Node::matches_class(*negated, ranges, text[i]);
+                }
+            }
+            Node::Star => {
+                let mut active = false;
+                for i in 0..=len {
+                    active = active || cur[i];
+                    next[i] = active;
+                    if i < len && text[i] == '/' {
+                        active = false;
+                    }
+                }
+            }
+            Node::RecursiveDirs => {
+                let mut seen = false;
+                for i in 0..=len {
+                    next[i] = cur[i] || seen && text[i - 1] == '/';
+                    seen = seen || cur[i];
+                }
+            }
+            Node::RecursiveAll => {
+                let mut seen = false;
+                for i in 0..=len {
+                    seen = seen || cur[i];
+                    next[i] = seen;
+                }
+            }
+            Node::Alt(alts) => {
+                for alt in alts {
+                    let reached = step(alt, text, cur.clone());
+                    for (n, r) in next.iter_mut().zip(reached) {
+                        *n = *n || r;
+                    }
+                }
+            }
+        }
+        cur = next;
+    }
+    cur
+}
+
+/// A compiled glob pattern to match slash paths.
+///
+/// See [the module document](self) for the syntax of glob patterns.
+///
+/// ```
+/// use path_slash::glob::SlashGlob;
+///
+/// let glob = SlashGlob::new("**/*.rs").unwrap();
+/// assert!(glob.is_match("lib.rs"));
+/// assert!(glob.is_match("src/foo/lib.rs"));
+/// assert!(!glob.is_match("src/foo/lib.rs.bak"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SlashGlob {
+    pattern: String,
+    negated: bool,
+    nodes: Vec<Node>,
+}
+
+impl SlashGlob {
+    /// Compile the glob pattern. When the pattern is invalid, this function returns an error.
+    ///
+    /// ```
+    /// use path_slash::glob::{GlobErrorKind, SlashGlob};
+    ///
+    /// assert!(SlashGlob::new("src/**/*.rs").is_ok());
+    ///
+    /// let err = SlashGlob::new("src/[a-z").unwrap_err();
+    /// assert_eq!(err.kind(), GlobErrorKind::UnclosedClass);
+    /// assert_eq!(err.pos(), 4);
+    /// ```
+    pub fn new(pattern: &str) -> Result<Self, GlobError> {
+        Self::with_options(
+            pattern,
+            Options {
+                braces: true,
+                negation: true,
+                strict_recursive: true,
+            },
+        )
+    }
+
+    pub(crate) fn with_options(pattern: &str, opts: Options) -> Result<Self, GlobError> {
+        let negated = opts.negation && pattern.starts_with('!');
+        let mut parser = Parser {
+            pattern,
+            chars: pattern.char_indices().collect(),
+            idx: if negated { 1 } else { 0 },
+            opts,
+        };
+        let nodes = parser.parse_seq(false, true)?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            negated,
+            nodes,
+        })
+    }
+
+    /// Return the original glob pattern.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Return `true` when the pattern is negated with the leading `!`.
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    // Match the slash path ignoring the negation
+    pub(crate) fn matches_body(&self, path: &str) -> bool {
+        let text = path.chars().collect::<Vec<_>>();
+        let mut start = vec![false; text.len() + 1];
+        start[0] = true;
+        step(&self.nodes, &text, start)[text.len()]
+    }
+
+    /// Return `true` when the slash path matches the glob pattern. When the pattern is negated,
+    /// the result is inverted.
+    ///
+    /// The whole path must match the pattern. The path is not normalized so `./foo` does not
+    /// match `foo`. Use [`SlashPath::clean`](crate::SlashPath::clean) before matching if necessary.
+    ///
+    /// ```
+    /// use path_slash::glob::SlashGlob;
+    ///
+    /// let glob = SlashGlob::new("src/*.rs").unwrap();
+    /// assert!(glob.is_match("src/lib.rs"));
+    /// assert!(!glob.is_match("src/foo/lib.rs"));
+    /// ```
+    pub fn is_match<P: AsRef<str> + ?Sized>(&self, path: &P) -> bool {
+        self.matches_body(path.as_ref()) != self.negated
+    }
+
+    /// Return `true` when the file path matches the glob pattern. The path is converted into a
+    /// slash path with [`PathExt::to_slash_lossy`](crate::PathExt::to_slash_lossy) before
+    /// matching. So non-Unicode sequences are replaced with U+FFFD.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::glob::SlashGlob;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// let p = Path::new(r"src\foo\lib.rs");
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// let p = Path::new("src/foo/lib.rs");
+    ///
+    /// assert!(SlashGlob::new("src/**/*.rs").unwrap().is_match_path(p));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn is_match_path<P: AsRef<Path> + ?Sized>(&self, path: &P) -> bool {
+        self.is_match(&*path.as_ref().to_slash_lossy())
+    }
+}
+
+impl fmt::Display for SlashGlob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
@@ -0,0 +1,301 @@
+//! Gitignore-style ignore rules evaluated on slash paths.
+//!
+//! [`IgnoreMatcher`] parses rule files such as `.gitignore`, `.dockerignore` and `.npmignore`.
+//! Rules are written with `/` separators and anchored relative to the directory of the rule
+//! file. File paths are converted with [`PathExt::to_slash`](crate::PathExt::to_slash) relative to
+//! the directory before matching, so the same rules behave in the same way on every OS.
+//!
+//! - Blank lines and lines starting with `#` are ignored
+//! - A leading `!` negates the rule. A path excluded by a previous rule is included again
+//! - A trailing `/` makes the rule match only directories
+//! - A rule containing `/` at the beginning or in the middle is anchored to the root directory.
+//!   Otherwise the rule matches at any level
+//! - `*`, `?`, `[...]` and `**` work in the same way as [`glob`](crate::glob) module, except
+//!   that `**` which is not a whole path component like `foo**` is treated as `*`
+//! - `\` escapes the next character such as `\#`, `\!` and `\ `
+//!
+//! When multiple rules match a path, the last one wins.
+//!
+//! ```
+//! use path_slash::ignore::IgnoreMatcher;
+//!
+//! let mut m = IgnoreMatcher::new("project");
+//! m.add_rules("/target\n*.log\n!important.log\nbuild/\n").unwrap();
+//!
+//! assert!(m.is_ignored("project/target/debug/foo", false));
+//! assert!(m.is_ignored("project/src/debug.log", false));
+//! assert!(!m.is_ignored("project/src/important.log", false));
+//! assert!(m.is_ignored("project/src/build", true));
+//! assert!(!m.is_ignored("project/src/build", false));
+//! assert!(!m.is_ignored("project/src/target", true));
+//! ```
+
+use crate::glob::{GlobError, Options, SlashGlob};
+use crate::{slash_path, PathExt as _, SlashPath};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// An error returned when reading ignore rules fails.
+#[derive(Debug)]
+pub enum IgnoreError {
+    /// Reading the rule file failed.
+    Io(io::Error),
+    /// The rule at the line (1-based) is not a valid glob pattern.
+    Glob {
+        /// The line number of the invalid rule.
+        line: usize,
+        /// The error on parsing the rule.
+        error: GlobError,
+    },
+}
+
+impl fmt::Display for IgnoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IgnoreError::Io(err) => write!(f, "could not read ignore file: {}", err),
+            IgnoreError::Glob { line, error } => {
+                write!(f, "invalid rule at line {}: {}", line, error)
+            }
+        }
+    }
+}
+
+impl Error for IgnoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IgnoreError::Io(err) => Some(err),
+            IgnoreError::Glob { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for IgnoreError {
+    fn from(err: io::Error) -> Self {
+        IgnoreError::Io(err)
+    }
+}
+
+/// The result of matching a path against ignore rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Match {
+    /// No rule matched the path.
+    None,
+    /// The last matched rule ignores the path.
+    Ignore,
+    /// The last matched rule is a negated rule (`!`) and includes the path again.
+    Whitelist,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    glob: SlashGlob,
+    negated: bool,
+    dir_only: bool,
+}
+
+// Remove trailing spaces unless they are escaped with '\'. A space is escaped when an odd number
+// of '\' precede it since '\\' is an escaped backslash
+fn trim_trailing_spaces(mut line: &str) -> &str {
+    while line.ends_with(' ') {
+        let rest = &line[..line.len() - 1];
+        let backslashes = rest.len() - rest.trim_end_matches('\\').len();
+        if backslashes % 2 == 1 {
+            break;
+        }
+        line = rest;
+    }
+    line
+}
+
+/// A set of gitignore-style ignore rules anchored to a root directory.
+///
+/// See [the module document](self) for the syntax of rules.
+#[derive(Clone, Debug)]
+pub struct IgnoreMatcher {
+    root: PathBuf,
+    rules: Vec<Rule>,
+}
+
+impl IgnoreMatcher {
+    /// Create an empty matcher whose rules are anchored to the `root` directory.
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            rules: vec![],
+        }
+    }
+
+    /// Read the rule file such as `.gitignore` and create a matcher. The rules are anchored to
+    /// the directory which contains the file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, IgnoreError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let root = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut matcher = Self::new(root);
+        matcher.add_rules(&content)?;
+        Ok(matcher)
+    }
+
+    /// Return the root directory of the rules.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Add a single rule. Blank lines and comments are accepted and ignored.
+    ///
+    /// ```
+    /// use path_slash::ignore::IgnoreMatcher;
+    ///
+    /// let mut m = IgnoreMatcher::new("");
+    /// m.add_rule("*.o").unwrap();
+    /// m.add_rule("# comment").unwrap();
+    /// assert!(m.is_ignored("foo/bar.o", false));
+    /// assert!(m.add_rule("[a-").is_err());
+    /// ```
+    pub fn add_rule(&mut self, line: &str) -> Result<(), GlobError> {
+        let line = trim_trailing_spaces(line.trim_end_matches('\r'));
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(());
+        }
+
+        let (negated, pattern) = if line.starts_with('!') {
+            (true, &line[1..])
+        } else {
+            (false, line)
+        };
+        let (dir_only, pattern) = if pattern.ends_with('/') && !pattern.ends_with("\\/") {
+            (true, pattern.trim_end_matches('/'))
+        } else {
+            (false, pattern)
+        };
+        if pattern.is_empty() {
+            return Ok(());
+        }
+
+        let glob = if pattern.starts_with('/') {
+            pattern[1..].to_string()
+        } else if pattern.contains('/') {
+            pattern.to_string()
+        } else {
+            format!("**/{}", pattern)
+        };
+        let opts = Options {
+            braces: false,
+            negation: false,
+            strict_recursive: false,
+        };
+        self.rules.push(Rule {
+            glob: SlashGlob::with_options(&glob, opts)?,
+            negated,
+            dir_only,
+        });
+        Ok(())
+    }
+
+    /// Add rules separated by newlines such as the content of `.gitignore` file.
+    pub fn add_rules(&mut self, content: &str) -> Result<(), IgnoreError> {
+        for (idx, line) in content.lines().enumerate() {
+            self.add_rule(line).map_err(|error| IgnoreError::Glob {
+                line: idx + 1,
+                error,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Match the slash path relative to the root directory against the rules. Only the path
+    /// itself is checked. Use [`IgnoreMatcher::is_ignored`] to also check its parent
+    /// directories.
+    ///
+    /// ```
+    /// use path_slash::ignore::{IgnoreMatcher, Match};
+    /// use path_slash::SlashPath;
+    ///
+    /// let mut m = IgnoreMatcher::new("");
+    /// m.add_rules("*.log\n!keep.log").unwrap();
+    /// assert_eq!(m.matched_slash(SlashPath::new("a/b.log").unwrap(), false), Match::Ignore);
+    /// assert_eq!(m.matched_slash(SlashPath::new("a/keep.log").unwrap(), false), Match::Whitelist);
+    /// assert_eq!(m.matched_slash(SlashPath::new("a/b.rs").unwrap(), false), Match::None);
+    /// ```
+    pub fn matched_slash(&self, path: &SlashPath, is_dir: bool) -> Match {
+        self.matched_str(path.as_str(), is_dir)
+    }
+
+    // File names on non-Windows OS may contain `\` so paths converted from `Path` are matched as
+    // plain strings
+    fn matched_str(&self, path: &str, is_dir: bool) -> Match {
+        for rule in self.rules.iter().rev() {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            if rule.glob.matches_body(path) {
+                return if rule.negated {
+                    Match::Whitelist
+                } else {
+                    Match::Ignore
+                };
+            }
+        }
+        Match::None
+    }
+
+    // Convert the path into a normalized slash path relative to the root directory. Returns
+    // `None` when the path is outside the root directory or is not valid Unicode.
+    fn relative_slash(&self, path: &Path) -> Option<String> {
+        let rel = match path.strip_prefix(&self.root) {
+            Ok(rel) => rel,
+            Err(_) if path.is_relative() && !path.has_root() => path,
+            Err(_) => return None,
+        };
+        let slash = slash_path::clean(&rel.to_slash()?);
+        if slash == "." || slash == ".." || slash.starts_with("../") || slash.starts_with('/') {
+            return None;
+        }
+        Some(slash)
+    }
+
+    /// Match the file path against the rules. The path is converted into a slash path relative
+    /// to the root directory. A relative path which does not start with the root directory is
+    /// treated as relative to the root directory. Only the path itself is checked. Use
+    /// [`IgnoreMatcher::is_ignored`] to also check its parent directories.
+    ///
+    /// Paths outside the root directory and paths which contain non-Unicode sequence never
+    /// match.
+    pub fn matched<P: AsRef<Path>>(&self, path: P, is_dir: bool) -> Match {
+        match self.relative_slash(path.as_ref()) {
+            Some(slash) => self.matched_str(&slash, is_dir),
+            None => Match::None,
+        }
+    }
+
+    /// Return `true` when the path is ignored by the rules. `is_dir` tells whether the path is
+    /// a directory.
+    ///
+    /// Like Git, a path is ignored when any of its parent directories is ignored. Negated rules
+    /// cannot include a path again when its parent directory is ignored.
+    ///
+    /// ```
+    /// use path_slash::ignore::IgnoreMatcher;
+    ///
+    /// let mut m = IgnoreMatcher::new("");
+    /// m.add_rules("build/\n!build/keep.txt").unwrap();
+    /// assert!(m.is_ignored("build", true));
+    /// assert!(m.is_ignored("build/keep.txt", false));
+    /// ```
+    pub fn is_ignored<P: AsRef<Path>>(&self, path: P, is_dir: bool) -> bool {
+        let slash = match self.relative_slash(path.as_ref()) {
+            Some(slash) => slash,
+            None => return false,
+        };
+
+        for (idx, _) in slash.match_indices('/') {
+            if self.matched_str(&slash[..idx], true) == Match::Ignore {
+                return true;
+            }
+        }
+        self.matched_str(&slash, is_dir) == Match::Ignore
+    }
+}
@@ -10,6 +10,23 @@
 //!
 //! Supported Rust version is 1.38.0 or later.
 //!
+//! [`SlashPath`] and [`SlashPathBuf`] are dedicated types for slash paths. They are a pair of
+//! borrowed and owned types like [`Path`](std::path::Path) and [`PathBuf`](std::path::PathBuf).
+//! `\` never appears in them except for a Windows path prefix such as `\\server\share`.
+//!
+//! To convert Windows paths or POSIX paths on any OS, the [`windows`] module and the [`posix`]
+//! module provide the same conversion on plain strings.
+//!
+//! To convert backslash paths which contain escape sequences such as `foo\ bar` in shell
+//! commands, use [`escape::BackslashConverter`].
+//!
+//! The conversions on `&str` and `String` work without the standard library. Disabling the
+//! default `std` feature makes this crate `no_std` with `alloc`. The extension traits for
+//! `std::path` types such as [`PathExt`] are only available with the `std` feature.
+//!
+//! With the `macros` feature, `slash_path!` and `concat_slash_path!` macros convert slash path
+//! literals into `&'static Path` at compile time. They require Rust 1.45 or later.
+//!
 //! This package was inspired by Go's [`path/filepath.FromSlash`](https://pkg.go.dev/path/filepath#FromSlash)
 //! and [`path/filepath.ToSlash`](https://pkg.go.dev/path/filepath#ToSlash).
 //!
@@ -62,26 +79,61 @@
 //!     assert_eq!(p.to_slash().unwrap(), "foo/bar/piyo.txt");
 //! }
 //! ```
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(unsafe_code)]
 #![warn(clippy::dbg_macro, clippy::print_stdout)]
 
-use std::borrow::Cow;
-use std::ffi::OsStr;
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::ffi::{OsStr, OsString};
+#[cfg(feature = "std")]
 use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+#[cfg(feature = "std")]
+use std::rc::Rc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
-#[cfg(target_os = "windows")]
-mod windows {
-    use super::*;
-    use std::os::windows::ffi::OsStrExt as _;
+pub mod compare;
+#[cfg(feature = "std")]
+mod display;
+pub mod escape;
+pub mod glob;
+#[cfg(feature = "std")]
+pub mod ignore;
+mod portable;
+pub mod posix;
+#[cfg(feature = "std")]
+mod relative;
+#[cfg(feature = "std")]
+mod safe_join;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod slash_path;
+mod style;
+pub mod uri;
+pub mod windows;
 
-    // Workaround for Windows. There is no way to extract raw byte sequence from `OsStr` (in `Path`).
-    // And `OsStr::to_string_lossy` may cause extra heap allocation.
-    pub(crate) fn ends_with_main_sep(p: &Path) -> bool {
-        p.as_os_str().encode_wide().last() == Some(MAIN_SEPARATOR as u16)
-    }
-}
+#[cfg(feature = "std")]
+pub use display::{DisplaySlash, NonUtf8};
+#[cfg(feature = "macros")]
+pub use path_slash_macros::{concat_slash_path, slash_path};
+pub use portable::{validate_portable, PortabilityError, PortabilityIssue, PortabilityIssueKind};
+#[cfg(feature = "std")]
+pub use relative::{diff_paths, diff_paths_canonicalized};
+#[cfg(feature = "std")]
+pub use safe_join::{safe_join, SafeJoinError};
+pub use slash_path::{SlashComponent, SlashComponents, SlashPath, SlashPathBuf};
+pub use style::{from_slash_as, to_slash_from, PathStyle};
 
-fn str_to_path(s: &str, sep: char) -> Cow<'_, Path> {
+#[cfg(feature = "std")]
+use uri::FileUriError;
+
+// Replace the separator `sep` in the string with `to`. Only when the replacement happens, heap
+// allocation happens and `Cow::Owned` is returned.
+fn replace_sep(s: &str, sep: char, to: char) -> Cow<'_, str> {
     let mut buf = String::new();
 
     for (i, c) in s.char_indices() {
@@ -90,32 +142,52 @@ fn str_to_path(s: &str, sep: char) -> Cow<'_, Path> {
                 buf.reserve(s.len());
                 buf.push_str(&s[..i]);
             }
-            buf.push(MAIN_SEPARATOR);
+            buf.push(to);
         } else if !buf.is_empty() {
             buf.push(c);
         }
     }
 
     if buf.is_empty() {
-        Cow::Borrowed(Path::new(s))
+        Cow::Borrowed(s)
     } else {
-        Cow::Owned(PathBuf::from(buf))
+        Cow::Owned(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+fn str_to_path(s: &str, sep: char) -> Cow<'_, Path> {
+    match replace_sep(s, sep, MAIN_SEPARATOR) {
+        Cow::Borrowed(s) => Cow::Borrowed(Path::new(s)),
+        Cow::Owned(s) => Cow::Owned(PathBuf::from(s)),
     }
 }
 
+#[cfg(feature = "std")]
 fn str_to_pathbuf<S: AsRef<str>>(s: S, sep: char) -> PathBuf {
-    let s = s
-        .as_ref()
-        .chars()
-        .map(|c| if c == sep { MAIN_SEPARATOR } else { c })
-        .collect::<String>();
-    PathBuf::from(s)
-    // Note: When MAIN_SEPARATOR_STR is stabilized, replace this implementation with the following:
-    // PathBuf::from(s.as_ref().replace(sep, MAIN_SEPARATOR_STR))
+    PathBuf::from(replace_sep(s.as_ref(), sep, MAIN_SEPARATOR).into_owned())
 }
 
+// Extension traits which are only implemented in this crate. New methods can be added to them
+// without breaking downstream crates.
+#[cfg(feature = "std")]
+mod sealed {
+    use std::ffi::OsStr;
+    use std::path::{Path, PathBuf};
+
+    pub trait Sealed {}
+
+    impl Sealed for Path {}
+    impl Sealed for OsStr {}
+    impl Sealed for PathBuf {}
+}
+
+#[cfg(feature = "std")]
 /// Trait to extend [`Path`].
 ///
+/// This trait is implemented for [`Path`] and [`OsStr`]. It is sealed and cannot be implemented
+/// outside this crate.
+///
 /// ```
 /// # use std::path::Path;
 /// # use std::borrow::Cow;
@@ -126,7 +198,7 @@ fn str_to_pathbuf<S: AsRef<str>>(s: S, sep: char) -> PathBuf {
 ///     Some(Cow::Borrowed("foo")),
 /// );
 /// ```
-pub trait PathExt {
+pub trait PathExt: sealed::Sealed {
     /// Convert the file path into slash path as UTF-8 string. This method is similar to
     /// [`Path::to_str`], but the path separator is fixed to '/'.
     ///
@@ -167,9 +239,226 @@ pub trait PathExt {
     /// assert_eq!(s.to_slash_lossy(), "foo/bar/piyo.txt");
     /// ```
     fn to_slash_lossy(&self) -> Cow<'_, str>;
+    /// Return an object which implements [`Display`](std::fmt::Display) to print the file path
+    /// as slash path. This is useful for printing paths with `format!` or logging macros.
+    ///
+    /// The output is the same as [`PathExt::to_slash_lossy`], but it is written to the formatter
+    /// directly and no heap allocation happens even on Windows.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::PathExt as _;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// let s = Path::new(r"foo\bar\piyo.txt");
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// let s = Path::new("foo/bar/piyo.txt");
+    ///
+    /// assert_eq!(format!("path: {}", s.display_slash()), "path: foo/bar/piyo.txt");
+    /// ```
+    fn display_slash(&self) -> DisplaySlash<'_>;
+    /// Append the file path as slash path to the string buffer. This is useful for reusing the
+    /// buffer when converting many paths.
+    ///
+    /// The appended string is the same as [`PathExt::to_slash`]. When the path contains
+    /// non-Unicode sequence, this method returns an error and the buffer is not modified.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::PathExt as _;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// let s = Path::new(r"foo\bar\piyo.txt");
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// let s = Path::new("foo/bar/piyo.txt");
+    ///
+    /// let mut buf = String::from("path: ");
+    /// s.write_slash(&mut buf).unwrap();
+    /// assert_eq!(buf, "path: foo/bar/piyo.txt");
+    /// ```
+    fn write_slash(&self, out: &mut String) -> Result<(), NonUtf8>;
+    /// Append the file path as slash path to the string buffer. Any non-Unicode sequences are
+    /// replaced with U+FFFD.
+    ///
+    /// The appended string is the same as [`PathExt::to_slash_lossy`].
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::PathExt as _;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// let s = Path::new(r"foo\bar\piyo.txt");
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// let s = Path::new("foo/bar/piyo.txt");
+    ///
+    /// let mut buf = String::new();
+    /// s.write_slash_lossy(&mut buf);
+    /// assert_eq!(buf, "foo/bar/piyo.txt");
+    /// ```
+    fn write_slash_lossy(&self, out: &mut String);
+    /// Convert the file path into slash path and lexically normalize it. This method is the
+    /// same as [`PathExt::to_slash`] followed by [`SlashPath::clean`]. This is the `normalize`
+    /// operation of file paths. There is no separate `normalize` method.
+    ///
+    /// `.` components and `..` components with their preceding components are removed, repeated
+    /// separators are collapsed and the trailing separator is removed. File systems are never
+    /// accessed so symbolic links are not resolved.
+    /// When the path contains non-Unicode sequence, this method returns None. On non-Windows OS,
+    /// it also returns None when a file name contains `\` as
+    /// [`SlashPathBuf::from_path`] does.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::PathExt as _;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// let s = Path::new(r"foo\.\bar\..\piyo.txt");
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// let s = Path::new("foo/./bar/../piyo.txt");
+    ///
+    /// assert_eq!(s.to_slash_clean().unwrap(), "foo/piyo.txt");
+    /// ```
+    fn to_slash_clean(&self) -> Option<SlashPathBuf>;
+    /// Convert the absolute file path into a `file://` URI such as `file:///C:/foo/bar%20baz`.
+    ///
+    /// The components of the path are joined with '/' and percent-encoded as described in
+    /// [RFC 8089](https://www.rfc-editor.org/rfc/rfc8089). On Windows, UNC paths such as
+    /// `\\server\share\foo` are converted into `file://server/share/foo`. The result is the same
+    /// as [`uri::to_file_uri`] with [`PathStyle::native`], which works on any OS.
+    ///
+    /// When the path is relative or contains non-Unicode sequence, this method returns an error.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::PathExt as _;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// let (s, u) = (Path::new(r"C:\foo\bar baz.txt"), "file:///C:/foo/bar%20baz.txt");
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// let (s, u) = (Path::new("/foo/bar baz.txt"), "file:///foo/bar%20baz.txt");
+    ///
+    /// assert_eq!(s.to_file_uri().unwrap(), u);
+    /// assert!(Path::new("foo").to_file_uri().is_err());
+    /// ```
+    fn to_file_uri(&self) -> Result<String, FileUriError>;
+    /// Convert the file path into slash path as a byte sequence. Unlike [`PathExt::to_slash`],
+    /// this method never fails and never loses information even if the path contains
+    /// non-Unicode sequence.
+    ///
+    /// This method is only available on Unix where a file path is an arbitrary byte sequence.
+    /// The path separator is already '/' so this method never allocates. The result can be
+    /// converted back with [`PathBufExt::from_slash_bytes`] without loss.
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::path::Path;
+    /// # use std::ffi::OsStr;
+    /// use std::os::unix::ffi::OsStrExt as _;
+    /// use path_slash::PathExt as _;
+    ///
+    /// let p = Path::new(OsStr::from_bytes(b"foo/\xff/bar.txt"));
+    /// assert_eq!(p.to_slash_bytes(), &b"foo/\xff/bar.txt"[..]);
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    fn to_slash_bytes(&self) -> Cow<'_, [u8]>;
+    /// Convert the file path into slash path as a UTF-16 sequence. Unlike [`PathExt::to_slash`],
+    /// this method never fails and never loses information even if the path contains unpaired
+    /// surrogates.
+    ///
+    /// This method is only available on Windows. The result can be converted back with
+    /// [`PathBufExt::from_slash_wide`] without loss. See [`windows::to_slash_wide`] for the
+    /// conversion on any OS.
+    ///
+    /// ```
+    /// # #[cfg(target_os = "windows")]
+    /// # {
+    /// # use std::path::Path;
+    /// use path_slash::PathExt as _;
+    ///
+    /// let p = Path::new(r"foo\bar");
+    /// assert_eq!(p.to_slash_wide(), "foo/bar".encode_utf16().collect::<Vec<_>>());
+    /// # }
+    /// ```
+    #[cfg(target_os = "windows")]
+    fn to_slash_wide(&self) -> Vec<u16>;
+    /// Convert the file path into slash path as [WTF-8](https://simonsapin.github.io/wtf-8/)
+    /// bytes. Unlike [`PathExt::to_slash`], this method never fails and never loses information
+    /// even if the path contains unpaired surrogates.
+    ///
+    /// This method is only available on Windows. The result can be converted back with
+    /// [`PathBufExt::from_slash_wtf8`] without loss. See [`windows::to_slash_wtf8`] for the
+    /// conversion on any OS.
+    ///
+    /// ```
+    /// # #[cfg(target_os = "windows")]
+    /// # {
+    /// # use std::path::Path;
+    /// use path_slash::PathExt as _;
+    ///
+    /// let p = Path::new(r"foo\bar");
+    /// assert_eq!(p.to_slash_wtf8(), b"foo/bar");
+    /// # }
+    /// ```
+    #[cfg(target_os = "windows")]
+    fn to_slash_wtf8(&self) -> Vec<u8>;
 }
 
+#[cfg(feature = "std")]
 impl PathExt for Path {
+    fn display_slash(&self) -> DisplaySlash<'_> {
+        DisplaySlash::new(self)
+    }
+
+    fn write_slash(&self, out: &mut String) -> Result<(), NonUtf8> {
+        // Separators are ASCII. So the slash path is UTF-8 if and only if the path is UTF-8
+        if self.to_str().is_none() {
+            return Err(NonUtf8);
+        }
+        self.write_slash_lossy(out);
+        Ok(())
+    }
+
+    fn write_slash_lossy(&self, out: &mut String) {
+        use std::fmt::Write as _;
+        // Writing to `String` never fails
+        let _ = write!(out, "{}", self.display_slash());
+    }
+
+    fn to_slash_clean(&self) -> Option<SlashPathBuf> {
+        self.to_slash()
+            .and_then(|s| SlashPath::new(&*s).map(SlashPath::clean))
+    }
+
+    fn to_file_uri(&self) -> Result<String, FileUriError> {
+        let path = self.to_str().ok_or(FileUriError::NonUnicode)?;
+        uri::to_file_uri(path, PathStyle::native())
+    }
+
+    #[cfg(unix)]
+    fn to_slash_bytes(&self) -> Cow<'_, [u8]> {
+        use std::os::unix::ffi::OsStrExt as _;
+        Cow::Borrowed(self.as_os_str().as_bytes())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn to_slash_wide(&self) -> Vec<u16> {
+        use std::os::windows::ffi::OsStrExt as _;
+        let wide = self.as_os_str().encode_wide().collect::<Vec<_>>();
+        windows::to_slash_wide(&wide)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn to_slash_wtf8(&self) -> Vec<u8> {
+        windows::wide_to_wtf8(&self.to_slash_wide())
+    }
+
     #[cfg(not(target_os = "windows"))]
     fn to_slash_lossy(&self) -> Cow<'_, str> {
         self.to_string_lossy()
@@ -233,8 +522,75 @@ impl PathExt for Path {
     }
 }
 
+/// [`OsStr`] is converted as a file path. Since [`OsString`], `Box<Path>`, `Rc<Path>` and
+/// `Arc<Path>` are dereferenced to [`OsStr`] or [`Path`], the methods can also be called on them
+/// directly.
+///
+/// ```
+/// # use std::ffi::OsString;
+/// # use std::sync::Arc;
+/// # use std::path::Path;
+/// use path_slash::PathExt as _;
+///
+/// #[cfg(not(target_os = "windows"))]
+/// let (s, p) = (OsString::from("foo/bar"), Arc::<Path>::from(Path::new("foo/bar")));
+/// #[cfg(target_os = "windows")]
+/// let (s, p) = (OsString::from(r"foo\bar"), Arc::<Path>::from(Path::new(r"foo\bar")));
+///
+/// assert_eq!(s.to_slash().unwrap(), "foo/bar");
+/// assert_eq!(p.to_slash_lossy(), "foo/bar");
+/// ```
+#[cfg(feature = "std")]
+impl PathExt for OsStr {
+    fn to_slash(&self) -> Option<Cow<'_, str>> {
+        Path::new(self).to_slash()
+    }
+
+    fn to_slash_lossy(&self) -> Cow<'_, str> {
+        Path::new(self).to_slash_lossy()
+    }
+
+    fn display_slash(&self) -> DisplaySlash<'_> {
+        Path::new(self).display_slash()
+    }
+
+    fn write_slash(&self, out: &mut String) -> Result<(), NonUtf8> {
+        Path::new(self).write_slash(out)
+    }
+
+    fn write_slash_lossy(&self, out: &mut String) {
+        Path::new(self).write_slash_lossy(out)
+    }
+
+    fn to_slash_clean(&self) -> Option<SlashPathBuf> {
+        Path::new(self).to_slash_clean()
+    }
+
+    fn to_file_uri(&self) -> Result<String, FileUriError> {
+        Path::new(self).to_file_uri()
+    }
+
+    #[cfg(unix)]
+    fn to_slash_bytes(&self) -> Cow<'_, [u8]> {
+        Path::new(self).to_slash_bytes()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn to_slash_wide(&self) -> Vec<u16> {
+        Path::new(self).to_slash_wide()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn to_slash_wtf8(&self) -> Vec<u8> {
+        Path::new(self).to_slash_wtf8()
+    }
+}
+
+#[cfg(feature = "std")]
 /// Trait to extend [`PathBuf`].
 ///
+/// This trait is sealed and cannot be implemented outside this crate.
+///
 /// ```
 /// # use std::path::PathBuf;
 /// use path_slash::PathBufExt as _;
@@ -244,7 +600,7 @@ impl PathExt for Path {
 ///     "foo/bar/piyo.txt",
 /// );
 /// ```
-pub trait PathBufExt {
+pub trait PathBufExt: sealed::Sealed {
     /// Convert the slash path (path separated with '/') to [`PathBuf`].
     ///
     /// Any '/' in the slash path is replaced with the file path separator.
@@ -266,6 +622,22 @@ pub trait PathBufExt {
     /// assert_eq!(p, PathBuf::from("foo/bar/piyo.txt"));
     /// ```
     fn from_slash<S: AsRef<str>>(s: S) -> Self;
+    /// Convert the slash path (path separated with '/') and store the result in the existing
+    /// [`PathBuf`]. The previous content of `buf` is cleared and its allocation is reused.
+    ///
+    /// The result is the same as [`PathBufExt::from_slash`].
+    ///
+    /// ```
+    /// # use std::path::PathBuf;
+    /// use path_slash::PathBufExt as _;
+    ///
+    /// let mut p = PathBuf::new();
+    /// for s in &["foo/bar", "foo/bar/piyo.txt"] {
+    ///     PathBuf::from_slash_into(&mut p, s);
+    ///     assert_eq!(p, PathBuf::from_slash(s));
+    /// }
+    /// ```
+    fn from_slash_into(buf: &mut Self, s: &str);
     /// Convert the [`OsStr`] slash path (path separated with '/') to [`PathBuf`].
     ///
     /// Any '/' in the slash path is replaced with the file path separator.
@@ -300,6 +672,92 @@ pub trait PathBufExt {
     ///
     /// Any '\\' in the slash path is replaced with the file path separator.
     fn from_backslash_lossy<S: AsRef<OsStr>>(s: S) -> Self;
+    /// Convert the slash path as a byte sequence to [`PathBuf`]. This is the reverse conversion
+    /// of [`PathExt::to_slash_bytes`].
+    ///
+    /// This function is only available on Unix where a file path is an arbitrary byte sequence.
+    /// Non-Unicode sequences are kept as they are.
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # {
+    /// # use std::path::PathBuf;
+    /// use std::os::unix::ffi::OsStrExt as _;
+    /// use path_slash::PathBufExt as _;
+    ///
+    /// let p = PathBuf::from_slash_bytes(b"foo/\xff/bar.txt");
+    /// assert_eq!(p.as_os_str().as_bytes(), b"foo/\xff/bar.txt");
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    fn from_slash_bytes<B: AsRef<[u8]>>(b: B) -> Self;
+    /// Convert the slash path as a UTF-16 sequence to [`PathBuf`]. This is the reverse
+    /// conversion of [`PathExt::to_slash_wide`].
+    ///
+    /// This function is only available on Windows. Unpaired surrogates are kept as they are.
+    ///
+    /// ```
+    /// # #[cfg(target_os = "windows")]
+    /// # {
+    /// # use std::path::PathBuf;
+    /// use path_slash::PathBufExt as _;
+    ///
+    /// let w = "foo/bar".encode_utf16().collect::<Vec<_>>();
+    /// assert_eq!(PathBuf::from_slash_wide(&w), PathBuf::from(r"foo\bar"));
+    /// # }
+    /// ```
+    #[cfg(target_os = "windows")]
+    fn from_slash_wide<W: AsRef<[u16]>>(w: W) -> Self;
+    /// Convert the `file://` URI into [`PathBuf`]. This is the reverse conversion of
+    /// [`PathExt::to_file_uri`].
+    ///
+    /// The URI is percent-decoded and converted with [`PathBufExt::from_slash`]. On Windows,
+    /// URIs with hosts such as `file://server/share/foo` are converted into UNC paths. See
+    /// [`uri::from_file_uri`] for the conversion on any OS.
+    ///
+    /// When the scheme of the URI is not `file` or the URI cannot be represented as an absolute
+    /// path, this function returns an error.
+    ///
+    /// ```
+    /// # use std::path::PathBuf;
+    /// use path_slash::PathBufExt as _;
+    /// use path_slash::uri::FileUriError;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// let (u, p) = ("file:///C:/foo/bar%20baz.txt", PathBuf::from(r"C:\foo\bar baz.txt"));
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// let (u, p) = ("file:///foo/bar%20baz.txt", PathBuf::from("/foo/bar baz.txt"));
+    ///
+    /// assert_eq!(PathBuf::from_file_uri(u).unwrap(), p);
+    /// assert_eq!(
+    ///     PathBuf::from_file_uri("http://example.com"),
+    ///     Err(FileUriError::UnsupportedScheme("http".to_string())),
+    /// );
+    /// ```
+    fn from_file_uri(uri: &str) -> Result<Self, FileUriError>
+    where
+        Self: Sized;
+    /// Convert the slash path as [WTF-8](https://simonsapin.github.io/wtf-8/) bytes to
+    /// [`PathBuf`]. This is the reverse conversion of [`PathExt::to_slash_wtf8`].
+    ///
+    /// This function is only available on Windows. Unpaired surrogates are kept as they are.
+    /// When the bytes are not valid WTF-8, this function returns `None`.
+    ///
+    /// ```
+    /// # #[cfg(target_os = "windows")]
+    /// # {
+    /// # use std::path::PathBuf;
+    /// use path_slash::PathBufExt as _;
+    ///
+    /// assert_eq!(PathBuf::from_slash_wtf8(b"foo/bar"), Some(PathBuf::from(r"foo\bar")));
+    /// assert_eq!(PathBuf::from_slash_wtf8(b"foo/\xff"), None);
+    /// # }
+    /// ```
+    #[cfg(target_os = "windows")]
+    fn from_slash_wtf8<B: AsRef<[u8]>>(b: B) -> Option<Self>
+    where
+        Self: Sized;
     /// Convert the file path into slash path as UTF-8 string. This method is similar to
     /// [`Path::to_str`], but the path separator is fixed to '/'.
     ///
@@ -342,6 +800,7 @@ pub trait PathBufExt {
     fn to_slash_lossy(&self) -> Cow<'_, str>;
 }
 
+#[cfg(feature = "std")]
 impl PathBufExt for PathBuf {
     #[cfg(not(target_os = "windows"))]
     fn from_slash<S: AsRef<str>>(s: S) -> Self {
@@ -352,6 +811,23 @@ impl PathBufExt for PathBuf {
         str_to_pathbuf(s, '/')
     }
 
+    fn from_slash_into(buf: &mut Self, s: &str) {
+        // Take the buffer out to reuse its allocation since `PathBuf::as_mut_os_string` is not
+        // available in Rust 1.38
+        let mut os = std::mem::replace(buf, PathBuf::new()).into_os_string();
+        os.clear();
+        #[cfg(not(target_os = "windows"))]
+        os.push(s);
+        #[cfg(target_os = "windows")]
+        for (i, part) in s.split('/').enumerate() {
+            if i > 0 {
+                os.push("\\");
+            }
+            os.push(part);
+        }
+        *buf = PathBuf::from(os);
+    }
+
     #[cfg(not(target_os = "windows"))]
     fn from_slash_lossy<S: AsRef<OsStr>>(s: S) -> Self {
         PathBuf::from(s.as_ref())
@@ -379,6 +855,29 @@ impl PathBufExt for PathBuf {
         PathBuf::from(s.as_ref())
     }
 
+    fn from_file_uri(uri: &str) -> Result<Self, FileUriError> {
+        let slash = uri::from_file_uri(uri, PathStyle::native())?;
+        Ok(Self::from_slash(slash))
+    }
+
+    #[cfg(unix)]
+    fn from_slash_bytes<B: AsRef<[u8]>>(b: B) -> Self {
+        use std::os::unix::ffi::OsStrExt as _;
+        PathBuf::from(OsStr::from_bytes(b.as_ref()))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn from_slash_wide<W: AsRef<[u16]>>(w: W) -> Self {
+        use std::ffi::OsString;
+        use std::os::windows::ffi::OsStringExt as _;
+        PathBuf::from(OsString::from_wide(&windows::from_slash_wide(w.as_ref())))
+    }
+
+    #[cfg(target_os = "windows")]
+    fn from_slash_wtf8<B: AsRef<[u8]>>(b: B) -> Option<Self> {
+        windows::wtf8_to_wide(b.as_ref()).map(Self::from_slash_wide)
+    }
+
     fn to_slash(&self) -> Option<Cow<'_, str>> {
         self.as_path().to_slash()
     }
@@ -388,6 +887,7 @@ impl PathBufExt for PathBuf {
     }
 }
 
+#[cfg(feature = "std")]
 /// Trait to extend [`Cow`].
 ///
 /// ```
@@ -504,6 +1004,7 @@ pub trait CowExt<'a> {
     fn to_slash_lossy(&self) -> Cow<'_, str>;
 }
 
+#[cfg(feature = "std")]
 impl<'a> CowExt<'a> for Cow<'a, Path> {
     #[cfg(not(target_os = "windows"))]
     fn from_slash(s: &'a str) -> Self {
@@ -555,3 +1056,64 @@ impl<'a> CowExt<'a> for Cow<'a, Path> {
         self.as_ref().to_slash_lossy()
     }
 }
+
+#[cfg(feature = "std")]
+/// Trait to create owned paths other than [`PathBuf`] from slash paths.
+///
+/// This is implemented for [`OsString`], `Box<Path>`, `Rc<Path>` and `Arc<Path>`. The
+/// conversions are the same as [`PathBufExt`].
+///
+/// ```
+/// # use std::ffi::OsString;
+/// # use std::path::{Path, PathBuf};
+/// # use std::sync::Arc;
+/// use path_slash::FromSlash as _;
+/// use path_slash::PathBufExt as _;
+///
+/// let p = Arc::<Path>::from_slash("foo/bar/piyo.txt");
+/// assert_eq!(&*p, PathBuf::from_slash("foo/bar/piyo.txt"));
+///
+/// let s = OsString::from_slash("foo/bar/piyo.txt");
+/// assert_eq!(s, PathBuf::from_slash("foo/bar/piyo.txt").into_os_string());
+/// ```
+pub trait FromSlash: Sized {
+    /// Convert the slash path (path separated with '/'). See [`PathBufExt::from_slash`].
+    fn from_slash<S: AsRef<str>>(s: S) -> Self;
+    /// Convert the [`OsStr`] slash path (path separated with '/'). See
+    /// [`PathBufExt::from_slash_lossy`].
+    fn from_slash_lossy<S: AsRef<OsStr>>(s: S) -> Self;
+    /// Convert the backslash path (path separated with '\\'). See
+    /// [`PathBufExt::from_backslash`].
+    fn from_backslash<S: AsRef<str>>(s: S) -> Self;
+    /// Convert the [`OsStr`] backslash path (path separated with '\\'). See
+    /// [`PathBufExt::from_backslash_lossy`].
+    fn from_backslash_lossy<S: AsRef<OsStr>>(s: S) -> Self;
+}
+
+macro_rules! impl_from_slash {
+    ($ty:ty, $from_path_buf:expr) => {
+        #[cfg(feature = "std")]
+        impl FromSlash for $ty {
+            fn from_slash<S: AsRef<str>>(s: S) -> Self {
+                $from_path_buf(PathBuf::from_slash(s))
+            }
+
+            fn from_slash_lossy<S: AsRef<OsStr>>(s: S) -> Self {
+                $from_path_buf(PathBuf::from_slash_lossy(s))
+            }
+
+            fn from_backslash<S: AsRef<str>>(s: S) -> Self {
+                $from_path_buf(PathBuf::from_backslash(s))
+            }
+
+            fn from_backslash_lossy<S: AsRef<OsStr>>(s: S) -> Self {
+                $from_path_buf(PathBuf::from_backslash_lossy(s))
+            }
+        }
+    };
+}
+
+impl_from_slash!(OsString, PathBuf::into_os_string);
+impl_from_slash!(Box<Path>, PathBuf::into_boxed_path);
+impl_from_slash!(Rc<Path>, Rc::<Path>::from);
+impl_from_slash!(Arc<Path>, Arc::<Path>::from);
@@ -0,0 +1,211 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+// File names longer than 255 bytes are rejected by most file systems such as ext4 and APFS
+const MAX_COMPONENT_LEN: usize = 255;
+// MAX_PATH on Windows is 260 including the terminating NUL
+const MAX_PATH_LEN: usize = 259;
+
+// https://learn.microsoft.com/en-us/windows/win32/fileio/naming-a-file#naming-conventions
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "CONIN$", "CONOUT$", "COM0", "COM1", "COM2", "COM3", "COM4",
+    "COM5", "COM6", "COM7", "COM8", "COM9", "COM¹", "COM²", "COM³", "LPT0", "LPT1", "LPT2", "LPT3",
+    "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9", "LPT¹", "LPT²", "LPT³",
+];
+
+/// The kind of [`PortabilityIssue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PortabilityIssueKind {
+    /// The component is a reserved device name on Windows such as `CON` or `NUL.txt`. Names are
+    /// reserved regardless of their cases and extensions.
+    ReservedName,
+    /// The character cannot be used in file names on Windows. It is one of `<>:"|?*\`.
+    ReservedChar(char),
+    /// The character is a control character such as NUL or a newline.
+    ControlChar(char),
+    /// The component ends with `.` or a space, which is removed silently on Windows.
+    TrailingDotOrSpace,
+    /// The component is longer than 255 bytes.
+    ComponentTooLong,
+    /// The whole path is longer than 259 bytes, which is `MAX_PATH` on Windows.
+    PathTooLong,
+}
+
+impl fmt::Display for PortabilityIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortabilityIssueKind::ReservedName => f.write_str("reserved file name on Windows"),
+            PortabilityIssueKind::ReservedChar(c) => write!(f, "reserved character {:?}", c),
+            PortabilityIssueKind::ControlChar(c) => write!(f, "control character {:?}", c),
+            PortabilityIssueKind::TrailingDotOrSpace => {
+                f.write_str("file name ends with dot or space")
+            }
+            PortabilityIssueKind::ComponentTooLong => {
+                write!(f, "file name is longer than {} bytes", MAX_COMPONENT_LEN)
+            }
+            PortabilityIssueKind::PathTooLong => {
+                write!(f, "path is longer than {} bytes", MAX_PATH_LEN)
+            }
+        }
+    }
+}
+
+/// A problem which prevents a slash path from being used on some platforms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortabilityIssue {
+    pos: usize,
+    kind: PortabilityIssueKind,
+}
+
+impl PortabilityIssue {
+    /// The kind of the problem.
+    pub fn kind(&self) -> PortabilityIssueKind {
+        self.kind
+    }
+
+    /// The byte offset in the slash path where the problem was found. For problems of whole
+    /// components such as [`PortabilityIssueKind::ReservedName`], this is the start of the
+    /// component.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl fmt::Display for PortabilityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte offset {}", self.kind, self.pos)
+    }
+}
+
+/// An error returned when a slash path is not portable. It contains all problems found in the
+/// path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PortabilityError {
+    path: String,
+    issues: Vec<PortabilityIssue>,
+}
+
+impl PortabilityError {
+    /// The problems found in the slash path in order of their offsets. This is never empty.
+    pub fn issues(&self) -> &[PortabilityIssue] {
+        &self.issues
+    }
+
+    /// The slash path which is not portable.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for PortabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "path {:?} is not portable: ", self.path)?;
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PortabilityError {}
+
+fn is_reserved_name(name: &str) -> bool {
+    // `NUL.txt` and `nul.tar.gz` are also reserved
+    let stem = match name.find('.') {
+        Some(i) => &name[..i],
+        None => name,
+    };
+    let stem = stem.trim_end_matches(' ');
+    RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(stem))
+}
+
+/// Check that the slash path can be used as a file path on all of Windows, macOS and Linux.
+///
+/// Each component separated with `/` is checked. The following problems are reported with
+/// their byte offsets:
+///
+/// - Reserved device names on Windows such as `CON`, `NUL.txt` or `com1`
+/// - Reserved characters on Windows `<>:"|?*` and `\`
+/// - Control characters U+0000 to U+001F and U+007F
+/// - Trailing `.` or space in a component. `.` and `..` components are allowed
+/// - A component longer than 255 bytes or a whole path longer than 259 bytes
+///
+/// Since a drive letter contains `:`, paths with Windows path prefixes are never portable.
+///
+/// ```
+/// use path_slash::{validate_portable, PortabilityIssueKind};
+///
+/// assert!(validate_portable("foo/bar/piyo.txt").is_ok());
+/// assert!(validate_portable("../foo/./bar").is_ok());
+///
+/// let err = validate_portable("logs/NUL.txt/a?b").unwrap_err();
+/// let issues = err.issues();
+/// assert_eq!(issues.len(), 2);
+/// assert_eq!(issues[0].kind(), PortabilityIssueKind::ReservedName);
+/// assert_eq!(issues[0].pos(), 5);
+/// assert_eq!(issues[1].kind(), PortabilityIssueKind::ReservedChar('?'));
+/// assert_eq!(issues[1].pos(), 14);
+/// ```
+pub fn validate_portable(slash: &str) -> Result<(), PortabilityError> {
+    let mut issues = vec![];
+    let mut start = 0;
+
+    for comp in slash.split('/') {
+        if comp.len() > MAX_COMPONENT_LEN {
+            issues.push(PortabilityIssue {
+                pos: start,
+                kind: PortabilityIssueKind::ComponentTooLong,
+            });
+        }
+        if start <= MAX_PATH_LEN && start + comp.len() > MAX_PATH_LEN {
+            issues.push(PortabilityIssue {
+                pos: start,
+                kind: PortabilityIssueKind::PathTooLong,
+            });
+        }
+        if is_reserved_name(comp) {
+            issues.push(PortabilityIssue {
+                pos: start,
+                kind: PortabilityIssueKind::ReservedName,
+            });
+        }
+
+        for (i, c) in comp.char_indices() {
+            let kind = match c {
+                '<' | '>' | ':' | '"' | '|' | '?' | '*' | '\\' => {
+                    PortabilityIssueKind::ReservedChar(c)
+                }
+                '\u{0}'..='\u{1f}' | '\u{7f}' => PortabilityIssueKind::ControlChar(c),
+                _ => continue,
+            };
+            issues.push(PortabilityIssue {
+                pos: start + i,
+                kind,
+            });
+        }
+
+        if comp != "." && comp != ".." && (comp.ends_with('.') || comp.ends_with(' ')) {
+            issues.push(PortabilityIssue {
+                pos: start + comp.len() - 1,
+                kind: PortabilityIssueKind::TrailingDotOrSpace,
+            });
+        }
+
+        start += comp.len() + 1;
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(PortabilityError {
+            path: slash.to_string(),
+            issues,
+        })
+    }
+}
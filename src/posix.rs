@@ -0,0 +1,195 @@
+//! Conversion between POSIX paths and slash paths which works on any OS.
+//!
+//! [`PathExt`](crate::PathExt) and the other traits interpret paths in the way of the OS where
+//! the program runs. The functions in this module treat paths as POSIX paths on every OS without
+//! consulting [`std::path`]. For example, they can interpret `/usr/lib/../x` on Windows.
+//!
+//! ```
+//! use path_slash::posix::{self, Component};
+//!
+//! assert_eq!(posix::from_backslash(r"usr\lib\..\x"), "usr/lib/../x");
+//! assert_eq!(
+//!     posix::components("/usr/lib/../x").collect::<Vec<_>>(),
+//!     [
+//!         Component::RootDir,
+//!         Component::Normal("usr"),
+//!         Component::Normal("lib"),
+//!         Component::ParentDir,
+//!         Component::Normal("x"),
+//!     ],
+//! );
+//! assert_eq!(posix::join("/usr/lib", "x"), "/usr/lib/x");
+//! ```
+
+use crate::{SlashComponent, SlashComponents};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::iter::FusedIterator;
+
+/// Convert the POSIX path into a slash path.
+///
+/// The path separator of POSIX paths is already '/'. So this function never allocates and the
+/// result is the same as [`PathExt::to_slash`](crate::PathExt::to_slash) on Unix-like OS.
+///
+/// ```
+/// use path_slash::posix;
+///
+/// assert_eq!(posix::to_slash("foo/bar/piyo.txt"), "foo/bar/piyo.txt");
+/// ```
+pub fn to_slash(path: &str) -> Cow<'_, str> {
+    Cow::Borrowed(path)
+}
+
+/// Convert the slash path (path separated with '/') into a POSIX path.
+///
+/// The path separator of POSIX paths is already '/'. So this function never allocates and the
+/// result is the same as [`PathBufExt::from_slash`](crate::PathBufExt::from_slash) on Unix-like
+/// OS.
+///
+/// ```
+/// use path_slash::posix;
+///
+/// assert_eq!(posix::from_slash("foo/bar/piyo.txt"), "foo/bar/piyo.txt");
+/// ```
+pub fn from_slash(slash: &str) -> Cow<'_, str> {
+    Cow::Borrowed(slash)
+}
+
+/// Convert the backslash path (path separated with '\\') into a POSIX path.
+///
+/// Any '\\' in the backslash path is replaced with '/'. The result is the same as
+/// [`PathBufExt::from_backslash`](crate::PathBufExt::from_backslash) on Unix-like OS. Only when
+/// the replacement happens, heap allocation happens and `Cow::Owned` is returned.
+///
+/// ```
+/// use path_slash::posix;
+///
+/// assert_eq!(posix::from_backslash(r"foo\bar\piyo.txt"), "foo/bar/piyo.txt");
+/// ```
+pub fn from_backslash(backslash: &str) -> Cow<'_, str> {
+    crate::replace_sep(backslash, '\\', '/')
+}
+
+/// Return `true` when the POSIX path is absolute, i.e. it starts with '/'.
+///
+/// ```
+/// use path_slash::posix;
+///
+/// assert!(posix::is_absolute("/usr/lib"));
+/// assert!(!posix::is_absolute("usr/lib"));
+/// assert!(!posix::is_absolute("C:/usr/lib"));
+/// ```
+pub fn is_absolute(path: &str) -> bool {
+    path.starts_with('/')
+}
+
+/// Join the POSIX path to the base path.
+///
+/// This works in the same way as [`PathBuf::push`](std::path::PathBuf::push) on Unix-like OS.
+/// When `path` is absolute, it replaces the base path.
+///
+/// ```
+/// use path_slash::posix;
+///
+/// assert_eq!(posix::join("foo", "bar"), "foo/bar");
+/// assert_eq!(posix::join("foo/", "bar"), "foo/bar");
+/// assert_eq!(posix::join("foo", "/bar"), "/bar");
+/// ```
+pub fn join(base: &str, path: &str) -> String {
+    if is_absolute(path) {
+        return path.to_string();
+    }
+    let mut buf = String::with_capacity(base.len() + path.len() + 1);
+    buf.push_str(base);
+    if !base.is_empty() && !base.ends_with('/') {
+        buf.push('/');
+    }
+    buf.push_str(path);
+    buf
+}
+
+/// A single component of a POSIX path. This is similar to [`std::path::Component`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Component<'a> {
+    /// The root directory `/`.
+    RootDir,
+    /// The current directory `.`. It only appears at the start of a relative path.
+    CurDir,
+    /// The parent directory `..`.
+    ParentDir,
+    /// A normal component such as `foo` in `/foo/bar`.
+    Normal(&'a str),
+}
+
+impl<'a> Component<'a> {
+    /// Extract the underlying string of the component.
+    ///
+    /// ```
+    /// use path_slash::posix;
+    ///
+    /// let names = posix::components("/foo/./bar/..").map(|c| c.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(names, ["/", "foo", "bar", ".."]);
+    /// ```
+    pub fn as_str(self) -> &'a str {
+        match self {
+            Component::RootDir => "/",
+            Component::CurDir => ".",
+            Component::ParentDir => "..",
+            Component::Normal(s) => s,
+        }
+    }
+}
+
+/// An iterator over the [`Component`]s of a POSIX path. This is created by [`components`].
+#[derive(Clone, Debug)]
+pub struct Components<'a> {
+    // POSIX paths are parsed in the same way as slash paths without Windows path prefixes
+    inner: SlashComponents<'a>,
+}
+
+/// Iterate over the components of the POSIX path.
+///
+/// Components are parsed in the same way as [`Path::components`](std::path::Path::components)
+/// on Unix-like OS. Repeated separators and `.` are ignored except for a `.` at the start of
+/// the path.
+///
+/// ```
+/// use path_slash::posix::{self, Component};
+///
+/// let mut c = posix::components("./foo//bar/.");
+/// assert_eq!(c.next(), Some(Component::CurDir));
+/// assert_eq!(c.next(), Some(Component::Normal("foo")));
+/// assert_eq!(c.next_back(), Some(Component::Normal("bar")));
+/// assert_eq!(c.next(), None);
+/// ```
+pub fn components(path: &str) -> Components<'_> {
+    Components {
+        inner: SlashComponents::new_posix(path),
+    }
+}
+
+fn from_slash_component(comp: SlashComponent<'_>) -> Component<'_> {
+    match comp {
+        SlashComponent::RootDir => Component::RootDir,
+        SlashComponent::CurDir => Component::CurDir,
+        SlashComponent::ParentDir => Component::ParentDir,
+        SlashComponent::Normal(s) => Component::Normal(s),
+        SlashComponent::Prefix(_) => unreachable!("POSIX path has no prefix"),
+    }
+}
+
+impl<'a> Iterator for Components<'a> {
+    type Item = Component<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(from_slash_component)
+    }
+}
+
+impl<'a> DoubleEndedIterator for Components<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(from_slash_component)
+    }
+}
+
+impl FusedIterator for Components<'_> {}
@@ -0,0 +1,61 @@
+use crate::{PathExt as _, SlashPath, SlashPathBuf};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Compute the relative path from `base` to `path` lexically and return it as a slash path.
+///
+/// Both paths are converted with [`PathExt::to_slash`](crate::PathExt::to_slash) and then the
+/// relative path is computed by [`SlashPath::relative_to`]. So the result is the same on every
+/// OS. File systems are never accessed so symbolic links are not resolved.
+///
+/// Returns `None` when either path cannot be converted into a slash path as
+/// [`SlashPathBuf::from_path`] or when the relative path cannot be computed. For example, when one path is absolute and the other is not or when
+/// their drive letters are different on Windows.
+///
+/// ```
+/// # use std::path::Path;
+/// use path_slash::diff_paths;
+///
+/// #[cfg(target_os = "windows")]
+/// let (path, base) = (Path::new(r"C:\foo\src\lib.rs"), Path::new(r"C:\foo\target\debug"));
+///
+/// #[cfg(not(target_os = "windows"))]
+/// let (path, base) = (Path::new("/foo/src/lib.rs"), Path::new("/foo/target/debug"));
+///
+/// assert_eq!(diff_paths(path, base).unwrap(), "../../src/lib.rs");
+/// ```
+pub fn diff_paths<P, B>(path: P, base: B) -> Option<SlashPathBuf>
+where
+    P: AsRef<Path>,
+    B: AsRef<Path>,
+{
+    let path = path.as_ref().to_slash()?;
+    let base = base.as_ref().to_slash()?;
+    SlashPath::new(&*path)?.relative_to(SlashPath::new(&*base)?.as_str())
+}
+
+/// Compute the relative path from `base` to `path` after canonicalizing both of them and return
+/// it as a slash path.
+///
+/// Both paths are canonicalized with [`std::fs::canonicalize`] so they must exist. Symbolic
+/// links are resolved. Then the relative path is computed in the same way as [`diff_paths`].
+///
+/// Returns an error when canonicalizing either path fails. Returns `Ok(None)` when the relative
+/// path cannot be computed.
+///
+/// ```
+/// use path_slash::diff_paths_canonicalized;
+///
+/// let rel = diff_paths_canonicalized("src/lib.rs", "tests/../src/..").unwrap();
+/// assert_eq!(rel.unwrap(), "src/lib.rs");
+/// ```
+pub fn diff_paths_canonicalized<P, B>(path: P, base: B) -> io::Result<Option<SlashPathBuf>>
+where
+    P: AsRef<Path>,
+    B: AsRef<Path>,
+{
+    let path = fs::canonicalize(path)?;
+    let base = fs::canonicalize(base)?;
+    Ok(diff_paths(path, base))
+}
@@ -0,0 +1,92 @@
+use crate::windows;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The reason why [`safe_join`] rejected a slash path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SafeJoinError {
+    /// The slash path is absolute such as `/etc/passwd`.
+    Absolute,
+    /// The slash path contains a Windows path prefix such as `C:`, `C:/` or `\\server\share`.
+    Prefix,
+    /// The slash path escapes the base directory with `..` components such as `../foo` or
+    /// `foo/../../bar`.
+    Traversal,
+    /// The slash path contains a NUL character.
+    Nul,
+}
+
+impl fmt::Display for SafeJoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            SafeJoinError::Absolute => "path is absolute",
+            SafeJoinError::Prefix => "path contains Windows path prefix",
+            SafeJoinError::Traversal => "path escapes the base directory",
+            SafeJoinError::Nul => "path contains NUL character",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for SafeJoinError {}
+
+/// Join the untrusted slash path onto the base directory without escaping it.
+///
+/// The slash path is checked lexically before joining. Absolute paths, Windows path prefixes such
+/// as `C:` or `\\server\share` in any component, NUL characters and `..` components which go
+/// above `base` are rejected with [`SafeJoinError`]. `..` components which stay inside `base`
+/// such as `a/../b` are resolved. `.` components and empty components are removed.
+///
+/// '\\' is also treated as a separator on every OS so that Windows-style paths such as
+/// `..\foo` in archives cannot escape `base`. File systems are never accessed so symbolic links
+/// inside `base` are not resolved.
+///
+/// ```
+/// # use std::path::{Path, PathBuf};
+/// use path_slash::{safe_join, SafeJoinError};
+///
+/// let base = Path::new("root");
+/// assert_eq!(safe_join(base, "a/b/../c.txt").unwrap(), base.join("a").join("c.txt"));
+/// assert_eq!(safe_join(base, "../etc/passwd"), Err(SafeJoinError::Traversal));
+/// assert_eq!(safe_join(base, r"a\..\..\x"), Err(SafeJoinError::Traversal));
+/// assert_eq!(safe_join(base, "/etc/passwd"), Err(SafeJoinError::Absolute));
+/// assert_eq!(safe_join(base, "C:/Windows"), Err(SafeJoinError::Prefix));
+/// ```
+pub fn safe_join<P, S>(base: P, slash: S) -> Result<PathBuf, SafeJoinError>
+where
+    P: AsRef<Path>,
+    S: AsRef<str>,
+{
+    let slash = slash.as_ref();
+    if slash.contains('\0') {
+        return Err(SafeJoinError::Nul);
+    }
+    if windows::parse_prefix(slash).is_some() {
+        return Err(SafeJoinError::Prefix);
+    }
+    if slash.starts_with(|c| c == '/' || c == '\\') {
+        return Err(SafeJoinError::Absolute);
+    }
+
+    let mut stack = vec![];
+    for comp in slash.split(|c| c == '/' || c == '\\') {
+        match comp {
+            "" | "." => {}
+            ".." => {
+                if stack.pop().is_none() {
+                    return Err(SafeJoinError::Traversal);
+                }
+            }
+            // On Windows, pushing `C:foo` would replace the whole path
+            _ if windows::parse_prefix(comp).is_some() => return Err(SafeJoinError::Prefix),
+            _ => stack.push(comp),
+        }
+    }
+
+    let mut path = base.as_ref().to_path_buf();
+    for comp in stack {
+        path.push(comp);
+    }
+    Ok(path)
+}
@@ -0,0 +1,193 @@
+//! Helpers to serialize and deserialize file paths as slash paths with [serde](https://serde.rs).
+//!
+//! This module is available when the `serde` feature is enabled. Each submodule provides
+//! `serialize` and `deserialize` functions for `#[serde(with = "...")]`,
+//! `#[serde(serialize_with = "...")]` and `#[serde(deserialize_with = "...")]` attributes.
+//!
+//! - [`slash`]: `PathBuf` field
+//! - [`slash_lossy`]: `PathBuf` field which may contain non-Unicode sequence
+//! - [`option`]: `Option<PathBuf>` field
+//! - [`vec`]: `Vec<PathBuf>` field
+//!
+//! Paths are serialized as strings via [`PathExt::to_slash`](crate::PathExt::to_slash) and
+//! deserialized via [`PathBufExt::from_slash`](crate::PathBufExt::from_slash). So the
+//! serialized paths are the same on every OS.
+//!
+//! For example, the fields of the following struct are serialized as slash paths.
+//!
+//! ```text
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "path_slash::serde::slash")]
+//!     out_dir: PathBuf,
+//!     #[serde(with = "path_slash::serde::option")]
+//!     cache_dir: Option<PathBuf>,
+//!     #[serde(with = "path_slash::serde::vec")]
+//!     sources: Vec<PathBuf>,
+//! }
+//! ```
+//!
+//! The derived implementations call the functions of the submodules as follows.
+//!
+//! ```
+//! # use serde_crate as serde;
+//! use path_slash::serde::{option, slash, vec};
+//! use path_slash::PathBufExt as _;
+//! use serde::de::value::{Error, SeqDeserializer, StrDeserializer, UnitDeserializer};
+//! use serde::de::IntoDeserializer as _;
+//! use std::path::PathBuf;
+//!
+//! let de: StrDeserializer<'_, Error> = "target/debug".into_deserializer();
+//! let out_dir = slash::deserialize(de).unwrap();
+//! assert_eq!(out_dir, PathBuf::from_slash("target/debug"));
+//!
+//! let de: UnitDeserializer<Error> = ().into_deserializer();
+//! let cache_dir = option::deserialize(de).unwrap();
+//! assert_eq!(cache_dir, None);
+//!
+//! let de: SeqDeserializer<_, Error> = vec!["src/lib.rs", "src/main.rs"].into_deserializer();
+//! let sources = vec::deserialize(de).unwrap();
+//! assert_eq!(
+//!     sources,
+//!     [PathBuf::from_slash("src/lib.rs"), PathBuf::from_slash("src/main.rs")],
+//! );
+//! ```
+
+use crate::{PathBufExt as _, PathExt as _};
+use serde_crate::de::{Deserialize, Deserializer};
+use serde_crate::ser::{Error as _, Serialize, Serializer};
+use std::path::{Path, PathBuf};
+
+struct Slash<'a>(&'a Path);
+
+impl Serialize for Slash<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.to_slash() {
+            Some(s) => serializer.serialize_str(&s),
+            None => Err(S::Error::custom(format_args!(
+                "path {:?} cannot be serialized as slash path since it contains non-Unicode \
+                 sequence",
+                self.0,
+            ))),
+        }
+    }
+}
+
+/// Serialize and deserialize a file path as a slash path.
+pub mod slash {
+    use super::*;
+
+    /// Serialize the file path as a slash path string with
+    /// [`PathExt::to_slash`](crate::PathExt::to_slash).
+    ///
+    /// This function fails when the path contains non-Unicode sequence.
+    pub fn serialize<P, S>(path: &P, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: AsRef<Path> + ?Sized,
+        S: Serializer,
+    {
+        Slash(path.as_ref()).serialize(serializer)
+    }
+
+    /// Deserialize a slash path string into a file path with
+    /// [`PathBufExt::from_slash`](crate::PathBufExt::from_slash).
+    ///
+    /// ```
+    /// # use serde_crate as serde;
+    /// use path_slash::serde::slash;
+    /// use serde::de::value::{Error, StrDeserializer};
+    /// use serde::de::IntoDeserializer as _;
+    /// # use std::path::PathBuf;
+    ///
+    /// let deserializer: StrDeserializer<'_, Error> = "foo/bar/piyo.txt".into_deserializer();
+    /// let path = slash::deserialize(deserializer).unwrap();
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// assert_eq!(path, PathBuf::from(r"foo\bar\piyo.txt"));
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// assert_eq!(path, PathBuf::from("foo/bar/piyo.txt"));
+    /// ```
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(PathBuf::from_slash(s))
+    }
+}
+
+/// Serialize and deserialize a file path as a slash path. Non-Unicode sequences are replaced
+/// with U+FFFD on serialization.
+pub mod slash_lossy {
+    use super::*;
+
+    /// Serialize the file path as a slash path string with
+    /// [`PathExt::to_slash_lossy`](crate::PathExt::to_slash_lossy).
+    ///
+    /// Unlike [`slash::serialize`](super::slash::serialize), this function never fails even if
+    /// the path contains non-Unicode sequence.
+    pub fn serialize<P, S>(path: &P, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: AsRef<Path> + ?Sized,
+        S: Serializer,
+    {
+        serializer.serialize_str(&path.as_ref().to_slash_lossy())
+    }
+
+    /// Deserialize a slash path string into a file path. This is the same as
+    /// [`slash::deserialize`](super::slash::deserialize).
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+        super::slash::deserialize(deserializer)
+    }
+}
+
+/// Serialize and deserialize an optional file path as an optional slash path.
+pub mod option {
+    use super::*;
+
+    /// Serialize the optional file path as an optional slash path string.
+    ///
+    /// This function fails when the path contains non-Unicode sequence.
+    pub fn serialize<P, S>(path: &Option<P>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: AsRef<Path>,
+        S: Serializer,
+    {
+        match path {
+            Some(path) => serializer.serialize_some(&Slash(path.as_ref())),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional slash path string into an optional file path.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<PathBuf>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = Option::<String>::deserialize(deserializer)?;
+        Ok(s.map(PathBuf::from_slash))
+    }
+}
+
+/// Serialize and deserialize a sequence of file paths as a sequence of slash paths.
+pub mod vec {
+    use super::*;
+
+    /// Serialize the file paths as a sequence of slash path strings.
+    ///
+    /// This function fails when any of the paths contains non-Unicode sequence.
+    pub fn serialize<P, S>(paths: &[P], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: AsRef<Path>,
+        S: Serializer,
+    {
+        serializer.collect_seq(paths.iter().map(|p| Slash(p.as_ref())))
+    }
+
+    /// Deserialize a sequence of slash path strings into file paths.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<PathBuf>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = Vec::<String>::deserialize(deserializer)?;
+        Ok(v.into_iter().map(PathBuf::from_slash).collect())
+    }
+}
@@ -0,0 +1,1235 @@
+use alloc::borrow::{Borrow, Cow, ToOwned};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+use crate::windows::{self, RawPrefix};
+#[cfg(feature = "std")]
+use crate::{CowExt as _, PathBufExt as _, PathExt as _};
+
+/// A slice of a slash path. This type is an unsized type like [`Path`].
+///
+/// A slash path is a UTF-8 string whose components are always separated by `/`. `\` only
+/// appears in a Windows path prefix such as `\\server\share` at the start of the path. The
+/// owned version of this type is [`SlashPathBuf`] like [`PathBuf`] is for [`Path`].
+///
+/// [`SlashPath::new`] checks the string and returns `None` when it contains `\` as a separator.
+/// To convert a file path into a slash path, use [`SlashPathBuf::from_path`].
+///
+/// ```
+/// use path_slash::SlashPath;
+///
+/// let p = SlashPath::new("foo/bar/piyo.txt").unwrap();
+/// assert_eq!(p.as_str(), "foo/bar/piyo.txt");
+///
+/// #[cfg(target_os = "windows")]
+/// assert_eq!(p.to_path_buf(), std::path::PathBuf::from(r"foo\bar\piyo.txt"));
+///
+/// #[cfg(not(target_os = "windows"))]
+/// assert_eq!(p.to_path_buf(), std::path::PathBuf::from("foo/bar/piyo.txt"));
+/// ```
+#[repr(transparent)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SlashPath {
+    inner: str,
+}
+
+impl SlashPath {
+    /// Wrap the string slice as a slash path slice. This is a cost-free conversion.
+    ///
+    /// Returns `None` when the string contains `\` outside a Windows path prefix since `\` is
+    /// never a separator of slash paths.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("foo/bar").unwrap(), "foo/bar");
+    /// assert_eq!(SlashPath::new(r"\\server\share/foo").unwrap(), r"\\server\share/foo");
+    /// assert_eq!(SlashPath::new(r"foo\bar"), None);
+    /// ```
+    pub fn new<S: AsRef<str> + ?Sized>(s: &S) -> Option<&SlashPath> {
+        let s = s.as_ref();
+        if is_slash_path(s) {
+            Some(SlashPath::new_unchecked(s))
+        } else {
+            None
+        }
+    }
+
+    // The caller must ensure that `is_slash_path(s)` is true
+    pub(crate) fn new_unchecked(s: &str) -> &SlashPath {
+        debug_assert!(is_slash_path(s), "{:?}", s);
+        // SAFETY: `SlashPath` is a transparent wrapper of `str`. So they have the same layout.
+        #[allow(unsafe_code)]
+        unsafe {
+            &*(s as *const str as *const SlashPath)
+        }
+    }
+
+    /// Get the underlying string slice of the slash path.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("foo/bar").unwrap().as_str(), "foo/bar");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    /// Convert the slash path into [`SlashPathBuf`] with heap allocation.
+    ///
+    /// ```
+    /// use path_slash::{SlashPath, SlashPathBuf};
+    ///
+    /// let p: SlashPathBuf = SlashPath::new("foo/bar").unwrap().to_slash_path_buf();
+    /// assert_eq!(p, "foo/bar");
+    /// ```
+    pub fn to_slash_path_buf(&self) -> SlashPathBuf {
+        SlashPathBuf {
+            inner: self.inner.to_string(),
+        }
+    }
+
+    /// Convert the slash path into a file path. '/' in the slash path is replaced with the file
+    /// path separator as [`CowExt::from_slash`](crate::CowExt::from_slash) does.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::SlashPath;
+    ///
+    /// let p = SlashPath::new("foo/bar/piyo.txt").unwrap().to_path();
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// assert_eq!(p, Path::new(r"foo\bar\piyo.txt"));
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// assert_eq!(p, Path::new("foo/bar/piyo.txt"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_path(&self) -> Cow<'_, Path> {
+        Cow::from_slash(&self.inner)
+    }
+
+    /// Convert the slash path into [`PathBuf`]. '/' in the slash path is replaced with the file
+    /// path separator as [`PathBufExt::from_slash`](crate::PathBufExt::from_slash) does.
+    ///
+    /// ```
+    /// # use std::path::PathBuf;
+    /// use path_slash::SlashPath;
+    ///
+    /// let p = SlashPath::new("foo/bar/piyo.txt").unwrap().to_path_buf();
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// assert_eq!(p, PathBuf::from(r"foo\bar\piyo.txt"));
+    ///
+    /// #[cfg(not(target_os = "windows"))]
+    /// assert_eq!(p, PathBuf::from("foo/bar/piyo.txt"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_path_buf(&self) -> PathBuf {
+        PathBuf::from_slash(&self.inner)
+    }
+
+    /// Produce an iterator over the [`SlashComponent`]s of the slash path. This is similar to
+    /// [`Path::components`].
+    ///
+    /// Repeated '/'s and `.`s are ignored except for a `.` at the start of a relative path. A
+    /// Windows path prefix such as `C:` or `\\server\share` at the start of the slash path is
+    /// yielded as [`SlashComponent::Prefix`] on any OS.
+    ///
+    /// ```
+    /// use path_slash::{SlashComponent, SlashPath};
+    ///
+    /// let mut c = SlashPath::new("C:/foo//bar/./piyo.txt").unwrap().components();
+    /// assert_eq!(c.next(), Some(SlashComponent::Prefix("C:")));
+    /// assert_eq!(c.next(), Some(SlashComponent::RootDir));
+    /// assert_eq!(c.next(), Some(SlashComponent::Normal("foo")));
+    /// assert_eq!(c.next_back(), Some(SlashComponent::Normal("piyo.txt")));
+    /// assert_eq!(c.as_path(), "bar");
+    /// ```
+    pub fn components(&self) -> SlashComponents<'_> {
+        SlashComponents::new(&self.inner)
+    }
+
+    /// Return `true` when the slash path is absolute as a POSIX path, i.e. it starts with `/`.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert!(SlashPath::new("/foo/bar").unwrap().is_absolute_posix());
+    /// assert!(!SlashPath::new("foo/bar").unwrap().is_absolute_posix());
+    /// assert!(!SlashPath::new("C:/foo").unwrap().is_absolute_posix());
+    /// ```
+    pub fn is_absolute_posix(&self) -> bool {
+        self.inner.starts_with('/')
+    }
+
+    /// Return `true` when the slash path is absolute as a Windows path. This is the same as
+    /// [`Path::is_absolute`] on Windows, but it works on any OS.
+    ///
+    /// A path is absolute when it has both a prefix and a root. `/foo` is not absolute since it
+    /// is relative to the drive of the current directory. `C:foo` is not absolute either since
+    /// it is relative to the current directory of the drive.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert!(SlashPath::new("C:/foo").unwrap().is_absolute_windows());
+    /// assert!(SlashPath::new(r"\\server\share/foo").unwrap().is_absolute_windows());
+    /// assert!(!SlashPath::new("/foo").unwrap().is_absolute_windows());
+    /// assert!(!SlashPath::new("C:foo").unwrap().is_absolute_windows());
+    /// ```
+    pub fn is_absolute_windows(&self) -> bool {
+        let comps = self.components();
+        comps.has_prefix() && comps.has_root()
+    }
+
+    /// Return `true` when the slash path has a root directory. This is the same as
+    /// [`Path::has_root`] on Windows, but it works on any OS.
+    ///
+    /// A path has a root when it starts with `/` or `C:/`, or when it starts with a prefix
+    /// which implies a root such as `\\server\share`. For POSIX paths, this is the same as
+    /// [`SlashPath::is_absolute_posix`].
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert!(SlashPath::new("/foo").unwrap().has_root());
+    /// assert!(SlashPath::new("C:/foo").unwrap().has_root());
+    /// assert!(SlashPath::new(r"\\server\share").unwrap().has_root());
+    /// assert!(!SlashPath::new("C:foo").unwrap().has_root());
+    /// assert!(!SlashPath::new("foo").unwrap().has_root());
+    /// ```
+    pub fn has_root(&self) -> bool {
+        self.components().has_root()
+    }
+
+    /// Return `true` when the slash path is relative to the current directory of a drive like
+    /// `C:foo`. Such a path has a drive letter but no root directory on Windows.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert!(SlashPath::new("C:foo").unwrap().is_drive_relative());
+    /// assert!(SlashPath::new("C:").unwrap().is_drive_relative());
+    /// assert!(!SlashPath::new("C:/foo").unwrap().is_drive_relative());
+    /// assert!(!SlashPath::new("foo").unwrap().is_drive_relative());
+    /// ```
+    pub fn is_drive_relative(&self) -> bool {
+        let comps = self.components();
+        match comps.prefix {
+            Some(RawPrefix::Disk) => !comps.has_root(),
+            _ => false,
+        }
+    }
+
+    /// Lexically normalize the slash path in the same way as Go's
+    /// [`path.Clean`](https://pkg.go.dev/path#Clean). This is the `normalize` operation of slash
+    /// paths. [`PathExt::to_slash_clean`](crate::PathExt::to_slash_clean) is the counterpart for
+    /// file paths.
+    ///
+    /// 1. Replace repeated '/'s with a single '/'
+    /// 2. Remove `.` components
+    /// 3. Remove `..` components with their preceding non-`..` components
+    /// 4. Remove `..` components just after the root directory
+    ///
+    /// The trailing '/' is removed except for the root directory and an empty path results in
+    /// `.`. A Windows path prefix such as `C:` or `\\server\share` is kept as-is. A UNC prefix is
+    /// only recognized in the `\\server\share` form. A leading `//` is collapsed into `/` as
+    /// `path.Clean` does. A verbatim path such as `\\?\C:/foo` is returned unchanged since
+    /// Windows never normalizes it. File systems are never accessed so symbolic links are not
+    /// resolved.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("a/./b/../c").unwrap().clean(), "a/c");
+    /// assert_eq!(SlashPath::new("../a//b/").unwrap().clean(), "../a/b");
+    /// assert_eq!(SlashPath::new("/../a").unwrap().clean(), "/a");
+    /// assert_eq!(SlashPath::new("C:/a/../..").unwrap().clean(), "C:/");
+    /// assert_eq!(SlashPath::new("//a/b/../c").unwrap().clean(), "/a/c");
+    /// assert_eq!(SlashPath::new("a/..").unwrap().clean(), ".");
+    /// assert_eq!(SlashPath::new(r"\\?\C:/a/../b").unwrap().clean(), r"\\?\C:/a/../b");
+    /// ```
+    pub fn clean(&self) -> SlashPathBuf {
+        SlashPathBuf {
+            inner: clean(&self.inner),
+        }
+    }
+
+    /// Compute the relative slash path from `base` to `self` lexically.
+    ///
+    /// Both paths are normalized with [`SlashPath::clean`] at first. Then `..` components are
+    /// prepended to the remaining part of `self` for each remaining component of `base`. File
+    /// systems are never accessed so symbolic links are not resolved.
+    ///
+    /// Returns `None` when the relative path cannot be computed. It happens when one path is
+    /// absolute and the other is not, when their Windows path prefixes (e.g. `C:` and `D:`) are
+    /// different, or when `base` contains `..` components which cannot be resolved lexically.
+    /// Windows path prefixes are compared case-insensitively.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// let p = SlashPath::new("/foo/src/lib.rs").unwrap();
+    /// assert_eq!(p.relative_to("/foo/target/debug").unwrap(), "../../src/lib.rs");
+    /// assert_eq!(p.relative_to("/foo").unwrap(), "src/lib.rs");
+    /// assert_eq!(p.relative_to("/foo/src/lib.rs").unwrap(), ".");
+    /// assert_eq!(p.relative_to("foo"), None);
+    ///
+    /// let p = SlashPath::new("C:/foo/bar").unwrap();
+    /// assert_eq!(p.relative_to("c:/foo/piyo").unwrap(), "../bar");
+    /// assert_eq!(p.relative_to("D:/foo"), None);
+    /// ```
+    pub fn relative_to<P: AsRef<str>>(&self, base: P) -> Option<SlashPathBuf> {
+        let path = self.clean();
+        let base = SlashPathBuf::from(base.as_ref()).clean();
+        let mut path_comps = path
+            .as_slash_path()
+            .components()
+            .filter(|c| *c != SlashComponent::CurDir);
+        let mut base_comps = base
+            .as_slash_path()
+            .components()
+            .filter(|c| *c != SlashComponent::CurDir);
+
+        let mut buf = SlashPathBuf::new();
+        loop {
+            match (path_comps.next(), base_comps.next()) {
+                (None, None) => break,
+                (Some(SlashComponent::Prefix(a)), Some(SlashComponent::Prefix(b)))
+                    if a.eq_ignore_ascii_case(b) => {}
+                (Some(a), Some(b)) if a == b => {}
+                (a, b) => {
+                    // Prefixes and root directories must be the same in both paths
+                    for c in a.iter().chain(b.iter()) {
+                        match c {
+                            SlashComponent::Prefix(_) | SlashComponent::RootDir => return None,
+                            _ => {}
+                        }
+                    }
+                    if let Some(b) = b {
+                        for c in Some(b).into_iter().chain(base_comps) {
+                            match c {
+                                SlashComponent::Normal(_) => buf.push(".."),
+                                _ => return None,
+                            }
+                        }
+                    }
+                    for c in a.into_iter().chain(path_comps) {
+                        buf.push(c.as_str());
+                    }
+                    break;
+                }
+            }
+        }
+
+        if buf.as_str().is_empty() {
+            buf.push(".");
+        }
+        Some(buf)
+    }
+
+    /// Return the slash path without its final component, if there is one. This is similar to
+    /// [`Path::parent`].
+    ///
+    /// Returns `None` if the path terminates in a root or prefix, or if it's the empty string.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("/foo/bar").unwrap().parent().unwrap(), "/foo");
+    /// assert_eq!(SlashPath::new("/foo").unwrap().parent().unwrap(), "/");
+    /// assert_eq!(SlashPath::new("foo").unwrap().parent().unwrap(), "");
+    /// assert_eq!(SlashPath::new("C:/").unwrap().parent(), None);
+    /// assert_eq!(SlashPath::new("/").unwrap().parent(), None);
+    /// ```
+    pub fn parent(&self) -> Option<&SlashPath> {
+        let mut comps = self.components();
+        match comps.next_back()? {
+            SlashComponent::Normal(_) | SlashComponent::CurDir | SlashComponent::ParentDir => {
+                Some(comps.as_path())
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the final component of the slash path, if there is one. This is similar to
+    /// [`Path::file_name`].
+    ///
+    /// Returns `None` if the path terminates in `..`.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("foo/bar.txt").unwrap().file_name(), Some("bar.txt"));
+    /// assert_eq!(SlashPath::new("foo/bar/.//").unwrap().file_name(), Some("bar"));
+    /// assert_eq!(SlashPath::new("foo/bar/..").unwrap().file_name(), None);
+    /// ```
+    pub fn file_name(&self) -> Option<&str> {
+        match self.components().next_back()? {
+            SlashComponent::Normal(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Extract the stem (non-extension) part of [`SlashPath::file_name`]. This is similar to
+    /// [`Path::file_stem`].
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("foo/bar.tar.gz").unwrap().file_stem(), Some("bar.tar"));
+    /// assert_eq!(SlashPath::new("foo/.bashrc").unwrap().file_stem(), Some(".bashrc"));
+    /// ```
+    pub fn file_stem(&self) -> Option<&str> {
+        let (before, after) = rsplit_file_at_dot(self.file_name()?);
+        before.or(after)
+    }
+
+    /// Extract the extension of [`SlashPath::file_name`], if possible. This is similar to
+    /// [`Path::extension`].
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("foo/bar.tar.gz").unwrap().extension(), Some("gz"));
+    /// assert_eq!(SlashPath::new("foo/.bashrc").unwrap().extension(), None);
+    /// ```
+    pub fn extension(&self) -> Option<&str> {
+        let (before, after) = rsplit_file_at_dot(self.file_name()?);
+        before.and(after)
+    }
+
+    /// Create an owned [`SlashPathBuf`] with `path` adjoined to `self`. This is similar to
+    /// [`Path::join`]. See [`SlashPathBuf::push`] for more details.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("foo").unwrap().join("bar/piyo.txt"), "foo/bar/piyo.txt");
+    /// assert_eq!(SlashPath::new("foo").unwrap().join("/bar"), "/bar");
+    /// ```
+    pub fn join<P: AsRef<str>>(&self, path: P) -> SlashPathBuf {
+        let mut buf = self.to_slash_path_buf();
+        buf.push(path);
+        buf
+    }
+
+    /// Create an owned [`SlashPathBuf`] like `self` but with the given file name. This is
+    /// similar to [`Path::with_file_name`]. See [`SlashPathBuf::set_file_name`] for more details.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("/foo/bar.txt").unwrap().with_file_name("piyo.rs"), "/foo/piyo.rs");
+    /// assert_eq!(SlashPath::new("/").unwrap().with_file_name("piyo.rs"), "/piyo.rs");
+    /// ```
+    pub fn with_file_name<S: AsRef<str>>(&self, file_name: S) -> SlashPathBuf {
+        let mut buf = self.to_slash_path_buf();
+        buf.set_file_name(file_name);
+        buf
+    }
+
+    /// Create an owned [`SlashPathBuf`] like `self` but with the given extension. This is
+    /// similar to [`Path::with_extension`]. See [`SlashPathBuf::set_extension`] for more
+    /// details.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// assert_eq!(SlashPath::new("foo/bar.txt").unwrap().with_extension("rs"), "foo/bar.rs");
+    /// assert_eq!(SlashPath::new("foo/bar.tar.gz").unwrap().with_extension(""), "foo/bar.tar");
+    /// ```
+    pub fn with_extension<S: AsRef<str>>(&self, extension: S) -> SlashPathBuf {
+        let mut buf = self.to_slash_path_buf();
+        buf.set_extension(extension);
+        buf
+    }
+}
+
+// Split the file name at the last dot. This is the same as `rsplit_file_at_dot` in `std::path`.
+fn rsplit_file_at_dot(file: &str) -> (Option<&str>, Option<&str>) {
+    if file == ".." {
+        return (Some(file), None);
+    }
+    let mut iter = file.rsplitn(2, '.');
+    let after = iter.next();
+    let before = iter.next();
+    if before == Some("") {
+        (Some(file), None)
+    } else {
+        (before, after)
+    }
+}
+
+pub(crate) fn clean(path: &str) -> String {
+    // `//server/share` is not treated as a UNC prefix. Otherwise `//a/b/..` would be cleaned to
+    // `//a/b/` and `..` could not remove `a` and `b`
+    let prefix =
+        windows::parse_prefix_bytes(path.as_bytes(), true).filter(|_| !path.starts_with('/'));
+    // Windows does not normalize verbatim paths. `.` and `..` in them are normal file names
+    if prefix.map_or(false, RawPrefix::is_verbatim) {
+        return path.to_string();
+    }
+    let (prefix_str, rest) = path.split_at(prefix.map_or(0, RawPrefix::len));
+    let rooted = rest.starts_with('/');
+
+    let mut parts = vec![];
+    for part in rest.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => match parts.last() {
+                Some(&last) if last != ".." => {
+                    parts.pop();
+                }
+                _ if rooted => { /* `..` at root is the root itself */ }
+                _ => parts.push(part),
+            },
+            _ => parts.push(part),
+        }
+    }
+
+    let mut buf = String::with_capacity(path.len());
+    buf.push_str(prefix_str);
+    if rooted {
+        buf.push('/');
+    }
+    buf.push_str(&parts.join("/"));
+    // `C:` is a relative path so it is cleaned to `C:.`. But UNC prefixes like `\\server\share`
+    // are absolute. Go's `filepath.Clean` also keeps them as-is.
+    if !rooted && parts.is_empty() && !prefix.map_or(false, RawPrefix::has_implicit_root) {
+        buf.push('.');
+    }
+    buf
+}
+
+// Check that `\` only appears in the Windows path prefix
+fn is_slash_path(s: &str) -> bool {
+    let prefix_len = windows::parse_prefix_bytes(s.as_bytes(), true).map_or(0, RawPrefix::len);
+    !s[prefix_len..].contains('\\')
+}
+
+// Replace `\` outside the Windows path prefix with `to`
+fn replace_backslashes(mut s: String, to: char) -> String {
+    let prefix_len = windows::parse_prefix_bytes(s.as_bytes(), true).map_or(0, RawPrefix::len);
+    if s[prefix_len..].contains('\\') {
+        let rest = s[prefix_len..].replace('\\', to.encode_utf8(&mut [0; 4]));
+        s.truncate(prefix_len);
+        s.push_str(&rest);
+    }
+    s
+}
+
+impl AsRef<str> for SlashPath {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl AsRef<SlashPath> for SlashPath {
+    fn as_ref(&self) -> &SlashPath {
+        self
+    }
+}
+
+impl ToOwned for SlashPath {
+    type Owned = SlashPathBuf;
+
+    fn to_owned(&self) -> SlashPathBuf {
+        self.to_slash_path_buf()
+    }
+}
+
+impl<'a> From<&'a SlashPath> for &'a str {
+    fn from(p: &'a SlashPath) -> Self {
+        &p.inner
+    }
+}
+
+impl fmt::Display for SlashPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Debug for SlashPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+/// An owned slash path. This type is similar to [`PathBuf`].
+///
+/// A slash path is a UTF-8 string whose components are always separated by `/`. This type
+/// dereferences to [`SlashPath`]. It can be converted from/to [`Path`] and [`PathBuf`] in the
+/// same way as [`PathExt::to_slash`](crate::PathExt::to_slash) and
+/// [`PathBufExt::from_slash`](crate::PathBufExt::from_slash).
+///
+/// When it is created from a string with [`From`], `\` outside a Windows path prefix is replaced
+/// with `/`.
+///
+/// ```
+/// # use std::path::Path;
+/// use path_slash::SlashPathBuf;
+///
+/// #[cfg(target_os = "windows")]
+/// let p = SlashPathBuf::from_path(Path::new(r"foo\bar\piyo.txt")).unwrap();
+///
+/// #[cfg(not(target_os = "windows"))]
+/// let p = SlashPathBuf::from_path(Path::new("foo/bar/piyo.txt")).unwrap();
+///
+/// assert_eq!(p, "foo/bar/piyo.txt");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SlashPathBuf {
+    inner: String,
+}
+
+impl SlashPathBuf {
+    /// Create a new empty slash path.
+    ///
+    /// ```
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// assert_eq!(SlashPathBuf::new(), "");
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert the file path into a slash path. This is similar to
+    /// [`PathExt::to_slash`](crate::PathExt::to_slash).
+    ///
+    /// When the path contains non-Unicode sequence, this method returns `None`. On non-Windows
+    /// OS, this method also returns `None` when a file name in the path contains `\` since it
+    /// cannot be represented in a slash path.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let p = SlashPathBuf::from_path(Path::new("foo")).unwrap();
+    /// assert_eq!(p, "foo");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_path<P: AsRef<Path> + ?Sized>(path: &P) -> Option<Self> {
+        let slash = path.as_ref().to_slash()?;
+        if !is_slash_path(&slash) {
+            return None;
+        }
+        Some(Self {
+            inner: slash.into_owned(),
+        })
+    }
+
+    /// Convert the file path into a slash path. This is similar to
+    /// [`PathExt::to_slash_lossy`](crate::PathExt::to_slash_lossy).
+    ///
+    /// Any non-Unicode sequences are replaced with U+FFFD. On non-Windows OS, `\` in file names
+    /// is also replaced with U+FFFD.
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let p = SlashPathBuf::from_path_lossy(Path::new("foo"));
+    /// assert_eq!(p, "foo");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_path_lossy<P: AsRef<Path> + ?Sized>(path: &P) -> Self {
+        let slash = path.as_ref().to_slash_lossy().into_owned();
+        Self {
+            inner: replace_backslashes(slash, '\u{FFFD}'),
+        }
+    }
+
+    /// Borrow the slash path as [`SlashPath`].
+    ///
+    /// ```
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let p = SlashPathBuf::from("foo/bar.txt");
+    /// assert_eq!(p.as_slash_path().file_name(), Some("bar.txt"));
+    /// ```
+    pub fn as_slash_path(&self) -> &SlashPath {
+        SlashPath::new_unchecked(&self.inner)
+    }
+
+    /// Convert the slash path into the underlying [`String`].
+    ///
+    /// ```
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let s: String = SlashPathBuf::from("foo/bar").into_string();
+    /// assert_eq!(s, "foo/bar");
+    /// ```
+    pub fn into_string(self) -> String {
+        self.inner
+    }
+
+    /// Extend `self` with `path`. This is similar to [`PathBuf::push`].
+    ///
+    /// When `path` has a Windows path prefix such as `C:`, it replaces `self`. When `path` has
+    /// a root, it replaces everything except for the prefix of `self`. Otherwise `path` is
+    /// appended to `self` with the separator '/'. `\` in `path` outside a Windows path prefix
+    /// is replaced with '/'.
+    ///
+    /// ```
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let mut p = SlashPathBuf::from("foo");
+    /// p.push("bar");
+    /// assert_eq!(p, "foo/bar");
+    /// p.push("/piyo");
+    /// assert_eq!(p, "/piyo");
+    ///
+    /// let mut p = SlashPathBuf::from("C:/foo");
+    /// p.push("/bar");
+    /// assert_eq!(p, "C:/bar");
+    /// p.push("D:/piyo");
+    /// assert_eq!(p, "D:/piyo");
+    /// ```
+    pub fn push<P: AsRef<str>>(&mut self, path: P) {
+        let path = path.as_ref();
+        let path = match SlashPath::new(path) {
+            Some(path) => Cow::Borrowed(path),
+            None => Cow::Owned(SlashPathBuf::from(path)),
+        };
+        let comps = self.as_slash_path().components();
+        let mut need_sep = !self.inner.is_empty() && !self.inner.ends_with('/');
+        // Do not add a separator after `C:` since `C:foo` is relative to the current directory
+        // of the drive
+        if comps.is_only_drive() {
+            need_sep = false;
+        }
+
+        let path_comps = path.components();
+        if path_comps.has_prefix() {
+            self.inner.clear();
+        } else if path_comps.has_root() {
+            let len = comps.prefix_len();
+            self.inner.truncate(len);
+        } else if need_sep {
+            self.inner.push('/');
+        }
+
+        self.inner.push_str(path.as_str());
+    }
+
+    /// Truncate `self` to [`SlashPath::parent`]. This is similar to [`PathBuf::pop`].
+    ///
+    /// Returns `false` and does nothing if [`SlashPath::parent`] is `None`.
+    ///
+    /// ```
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let mut p = SlashPathBuf::from("/foo/bar");
+    /// assert!(p.pop());
+    /// assert_eq!(p, "/foo");
+    /// assert!(p.pop());
+    /// assert_eq!(p, "/");
+    /// assert!(!p.pop());
+    /// ```
+    pub fn pop(&mut self) -> bool {
+        match self.as_slash_path().parent().map(|p| p.as_str().len()) {
+            Some(len) => {
+                self.inner.truncate(len);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Update [`SlashPath::file_name`] to `file_name`. This is similar to
+    /// [`PathBuf::set_file_name`].
+    ///
+    /// If [`SlashPath::file_name`] was `None`, this is equivalent to pushing `file_name`.
+    /// Otherwise it is equivalent to calling [`SlashPathBuf::pop`] and then pushing `file_name`.
+    ///
+    /// ```
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let mut p = SlashPathBuf::from("/foo/bar.txt");
+    /// p.set_file_name("piyo.rs");
+    /// assert_eq!(p, "/foo/piyo.rs");
+    /// ```
+    pub fn set_file_name<S: AsRef<str>>(&mut self, file_name: S) {
+        if self.as_slash_path().file_name().is_some() {
+            let popped = self.pop();
+            debug_assert!(popped);
+        }
+        self.push(file_name.as_ref());
+    }
+
+    /// Update [`SlashPath::extension`] to `extension`. This is similar to
+    /// [`PathBuf::set_extension`].
+    ///
+    /// Returns `false` and does nothing if [`SlashPath::file_name`] is `None`. Otherwise returns
+    /// `true` and updates the extension. When `extension` is empty, the extension is removed.
+    ///
+    /// ```
+    /// use path_slash::SlashPathBuf;
+    ///
+    /// let mut p = SlashPathBuf::from("foo/bar.txt");
+    /// assert!(p.set_extension("rs"));
+    /// assert_eq!(p, "foo/bar.rs");
+    /// assert!(p.set_extension(""));
+    /// assert_eq!(p, "foo/bar");
+    /// ```
+    pub fn set_extension<S: AsRef<str>>(&mut self, extension: S) -> bool {
+        let end = match self.as_slash_path().file_stem() {
+            Some(stem) => stem.as_ptr() as usize - self.inner.as_ptr() as usize + stem.len(),
+            None => return false,
+        };
+
+        // Remove the old extension and any trailing separators
+        self.inner.truncate(end);
+
+        let extension = extension.as_ref();
+        if !extension.is_empty() {
+            self.inner.push('.');
+            self.inner.push_str(extension);
+        }
+        true
+    }
+
+    /// Convert the slash path into [`PathBuf`]. '/' in the slash path is replaced with the file
+    /// path separator as [`PathBufExt::from_slash`](crate::PathBufExt::from_slash) does.
+    ///
+    /// On non-Windows OS, the underlying buffer is reused and no heap allocation happens.
+    #[cfg(feature = "std")]
+    pub fn into_path_buf(self) -> PathBuf {
+        #[cfg(not(target_os = "windows"))]
+        {
+            PathBuf::from(self.inner)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            PathBuf::from_slash(self.inner)
+        }
+    }
+}
+
+impl Deref for SlashPathBuf {
+    type Target = SlashPath;
+
+    fn deref(&self) -> &SlashPath {
+        self.as_slash_path()
+    }
+}
+
+impl AsRef<str> for SlashPathBuf {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl AsRef<SlashPath> for SlashPathBuf {
+    fn as_ref(&self) -> &SlashPath {
+        self.as_slash_path()
+    }
+}
+
+impl Borrow<SlashPath> for SlashPathBuf {
+    fn borrow(&self) -> &SlashPath {
+        self.as_slash_path()
+    }
+}
+
+// `Hash`, `Eq` and `Ord` of `SlashPathBuf` are the same as `str` so it can be borrowed as `str`
+// for looking up maps and sets
+impl Borrow<str> for SlashPathBuf {
+    fn borrow(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl From<String> for SlashPathBuf {
+    fn from(inner: String) -> Self {
+        Self {
+            inner: replace_backslashes(inner, '/'),
+        }
+    }
+}
+
+impl From<&str> for SlashPathBuf {
+    fn from(s: &str) -> Self {
+        Self::from(s.to_string())
+    }
+}
+
+impl From<&SlashPath> for SlashPathBuf {
+    fn from(p: &SlashPath) -> Self {
+        p.to_slash_path_buf()
+    }
+}
+
+impl<'a> From<Cow<'a, SlashPath>> for SlashPathBuf {
+    fn from(p: Cow<'a, SlashPath>) -> Self {
+        p.into_owned()
+    }
+}
+
+impl From<SlashPathBuf> for String {
+    fn from(p: SlashPathBuf) -> Self {
+        p.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<SlashPathBuf> for PathBuf {
+    fn from(p: SlashPathBuf) -> Self {
+        p.into_path_buf()
+    }
+}
+
+impl fmt::Display for SlashPathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl fmt::Debug for SlashPathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.inner, f)
+    }
+}
+
+macro_rules! impl_cmp {
+    ($lhs:ty, $rhs:ty) => {
+        impl<'a> PartialEq<$rhs> for $lhs {
+            fn eq(&self, other: &$rhs) -> bool {
+                <str as PartialEq>::eq(self.as_ref(), other.as_ref())
+            }
+        }
+
+        impl<'a> PartialEq<$lhs> for $rhs {
+            fn eq(&self, other: &$lhs) -> bool {
+                <str as PartialEq>::eq(self.as_ref(), other.as_ref())
+            }
+        }
+    };
+}
+
+impl_cmp!(SlashPathBuf, SlashPath);
+impl_cmp!(SlashPathBuf, &'a SlashPath);
+impl_cmp!(SlashPath, str);
+impl_cmp!(SlashPath, &'a str);
+impl_cmp!(SlashPath, String);
+impl_cmp!(&'a SlashPath, str);
+impl_cmp!(&'a SlashPath, String);
+impl_cmp!(SlashPathBuf, str);
+impl_cmp!(SlashPathBuf, &'a str);
+impl_cmp!(SlashPathBuf, String);
+
+/// A single component of a slash path. This is similar to [`std::path::Component`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SlashComponent<'a> {
+    /// A Windows path prefix such as `C:`, `\\server\share` or `\\?\C:`.
+    Prefix(&'a str),
+    /// The root directory `/`.
+    RootDir,
+    /// The current directory `.`. It only appears at the start of a relative path.
+    CurDir,
+    /// The parent directory `..`.
+    ParentDir,
+    /// A normal component such as `foo` in `/foo/bar`.
+    Normal(&'a str),
+}
+
+impl<'a> SlashComponent<'a> {
+    /// Extract the underlying string of the component.
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// let names = SlashPath::new("C:/foo/./bar/..").unwrap()
+    ///     .components()
+    ///     .map(|c| c.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["C:", "/", "foo", "bar", ".."]);
+    /// ```
+    pub fn as_str(self) -> &'a str {
+        match self {
+            SlashComponent::Prefix(s) | SlashComponent::Normal(s) => s,
+            SlashComponent::RootDir => "/",
+            SlashComponent::CurDir => ".",
+            SlashComponent::ParentDir => "..",
+        }
+    }
+}
+
+impl AsRef<str> for SlashComponent<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum State {
+    Prefix = 0,
+    StartDir = 1,
+    Body = 2,
+    Done = 3,
+}
+
+/// An iterator over the [`SlashComponent`]s of a slash path. This is created by
+/// [`SlashPath::components`].
+///
+/// This iterator is double-ended and parses components in the same way as
+/// [`std::path::Components`].
+#[derive(Clone)]
+pub struct SlashComponents<'a> {
+    // The remaining slash path which has not been parsed yet
+    path: &'a str,
+    prefix: Option<RawPrefix<'a>>,
+    has_physical_root: bool,
+    front: State,
+    back: State,
+}
+
+impl<'a> SlashComponents<'a> {
+    fn new(path: &'a str) -> Self {
+        Self::with_prefix(path, windows::parse_prefix_bytes(path.as_bytes(), true))
+    }
+
+    // POSIX paths have no prefix. For example, `//server/share` is a root directory followed by
+    // two normal components
+    pub(crate) fn new_posix(path: &'a str) -> Self {
+        Self::with_prefix(path, None)
+    }
+
+    fn with_prefix(path: &'a str, prefix: Option<RawPrefix<'a>>) -> Self {
+        let prefix_len = prefix.map_or(0, RawPrefix::len);
+        Self {
+            path,
+            prefix,
+            has_physical_root: path[prefix_len..].starts_with('/'),
+            front: State::Prefix,
+            back: State::Body,
+        }
+    }
+
+    /// Extract the remaining part of the slash path as [`SlashPath`].
+    ///
+    /// ```
+    /// use path_slash::SlashPath;
+    ///
+    /// let mut c = SlashPath::new("/foo/bar/./").unwrap().components();
+    /// c.next();
+    /// assert_eq!(c.as_path(), "foo/bar");
+    /// c.next_back();
+    /// assert_eq!(c.as_path(), "foo");
+    /// ```
+    pub fn as_path(&self) -> &'a SlashPath {
+        let mut comps = self.clone();
+        if comps.front == State::Body {
+            comps.trim_left();
+        }
+        if comps.back == State::Body {
+            comps.trim_right();
+        }
+        SlashPath::new_unchecked(comps.path)
+    }
+
+    fn prefix_len(&self) -> usize {
+        self.prefix.map_or(0, RawPrefix::len)
+    }
+
+    fn has_prefix(&self) -> bool {
+        self.prefix.is_some()
+    }
+
+    fn is_only_drive(&self) -> bool {
+        match self.prefix {
+            Some(RawPrefix::Disk) => self.prefix_len() == self.path.len(),
+            _ => false,
+        }
+    }
+
+    fn prefix_remaining(&self) -> usize {
+        if self.front == State::Prefix {
+            self.prefix_len()
+        } else {
+            0
+        }
+    }
+
+    // A verbatim prefix has an implicit root but it is not yielded as `RootDir`
+    fn has_implicit_root_dir(&self) -> bool {
+        self.prefix
+            .map_or(false, |p| p.has_implicit_root() && !p.is_verbatim())
+    }
+
+    fn has_root(&self) -> bool {
+        self.has_physical_root || self.prefix.map_or(false, RawPrefix::has_implicit_root)
+    }
+
+    // Like `std::path::Components`, `CurDir` is never yielded after a prefix. `C:./foo` is
+    // [Prefix, Normal]
+    fn include_cur_dir(&self) -> bool {
+        if self.has_root() || self.has_prefix() {
+            return false;
+        }
+        let rest = &self.path[self.prefix_remaining()..];
+        rest == "." || rest.starts_with("./")
+    }
+
+    fn len_before_body(&self) -> usize {
+        let mut len = self.prefix_remaining();
+        if self.front <= State::StartDir {
+            if self.has_physical_root {
+                len += 1;
+            }
+            if self.include_cur_dir() {
+                len += 1;
+            }
+        }
+        len
+    }
+
+    fn finished(&self) -> bool {
+        self.front == State::Done || self.back == State::Done || self.front > self.back
+    }
+
+    fn parse_single_component(comp: &'a str) -> Option<SlashComponent<'a>> {
+        match comp {
+            "" | "." => None,
+            ".." => Some(SlashComponent::ParentDir),
+            _ => Some(SlashComponent::Normal(comp)),
+        }
+    }
+
+    // Parse the next component from the front. Return the byte length to be consumed.
+    fn parse_next_component(&self) -> (usize, Option<SlashComponent<'a>>) {
+        let path = self.path;
+        let (extra, comp) = match path.find('/') {
+            Some(i) => (1, &path[..i]),
+            None => (0, path),
+        };
+        (comp.len() + extra, Self::parse_single_component(comp))
+    }
+
+    // Parse the next component from the back. Return the byte length to be consumed.
+    fn parse_next_component_back(&self) -> (usize, Option<SlashComponent<'a>>) {
+        let path = &self.path[self.len_before_body()..];
+        let (extra, comp) = match path.rfind('/') {
+            Some(i) => (1, &path[i + 1..]),
+            None => (0, path),
+        };
+        (comp.len() + extra, Self::parse_single_component(comp))
+    }
+
+    fn trim_left(&mut self) {
+        while !self.path.is_empty() {
+            let (size, comp) = self.parse_next_component();
+            if comp.is_some() {
+                return;
+            }
+            self.path = &self.path[size..];
+        }
+    }
+
+    fn trim_right(&mut self) {
+        while self.path.len() > self.len_before_body() {
+            let (size, comp) = self.parse_next_component_back();
+            if comp.is_some() {
+                return;
+            }
+            self.path = &self.path[..self.path.len() - size];
+        }
+    }
+}
+
+impl<'a> Iterator for SlashComponents<'a> {
+    type Item = SlashComponent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished() {
+            match self.front {
+                State::Prefix => {
+                    self.front = State::StartDir;
+                    let len = self.prefix_len();
+                    if len > 0 {
+                        let (prefix, rest) = self.path.split_at(len);
+                        self.path = rest;
+                        return Some(SlashComponent::Prefix(prefix));
+                    }
+                }
+                State::StartDir => {
+                    self.front = State::Body;
+                    if self.has_physical_root {
+                        self.path = &self.path[1..];
+                        return Some(SlashComponent::RootDir);
+                    } else if self.has_implicit_root_dir() {
+                        return Some(SlashComponent::RootDir);
+                    } else if self.include_cur_dir() {
+                        self.path = &self.path[1..];
+                        return Some(SlashComponent::CurDir);
+                    }
+                }
+                State::Body if !self.path.is_empty() => {
+                    let (size, comp) = self.parse_next_component();
+                    self.path = &self.path[size..];
+                    if comp.is_some() {
+                        return comp;
+                    }
+                }
+                State::Body => self.front = State::Done,
+                State::Done => unreachable!(),
+            }
+        }
+        None
+    }
+}
+
+impl<'a> DoubleEndedIterator for SlashComponents<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while !self.finished() {
+            match self.back {
+                State::Body if self.path.len() > self.len_before_body() => {
+                    let (size, comp) = self.parse_next_component_back();
+                    self.path = &self.path[..self.path.len() - size];
+                    if comp.is_some() {
+                        return comp;
+                    }
+                }
+                State::Body => self.back = State::StartDir,
+                State::StartDir => {
+                    self.back = State::Prefix;
+                    if self.has_physical_root {
+                        self.path = &self.path[..self.path.len() - 1];
+                        return Some(SlashComponent::RootDir);
+                    } else if self.has_implicit_root_dir() {
+                        return Some(SlashComponent::RootDir);
+                    } else if self.include_cur_dir() {
+                        self.path = &self.path[..self.path.len() - 1];
+                        return Some(SlashComponent::CurDir);
+                    }
+                }
+                State::Prefix => {
+                    self.back = State::Done;
+                    if self.prefix.is_some() {
+                        return Some(SlashComponent::Prefix(self.path));
+                    }
+                }
+                State::Done => unreachable!(),
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for SlashComponents<'_> {}
+
+impl fmt::Debug for SlashComponents<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
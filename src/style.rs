@@ -0,0 +1,87 @@
+use crate::{posix, windows};
+use alloc::string::String;
+
+/// The style of native paths on an OS.
+///
+/// [`PathBufExt::from_slash`](crate::PathBufExt::from_slash) and the other traits always
+/// convert paths in the style of the OS where the program runs. Passing `PathStyle` to
+/// [`from_slash_as`] and [`to_slash_from`] chooses the style explicitly instead. This is useful
+/// when generating paths for another OS such as cross compilation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathStyle {
+    /// POSIX paths separated with '/' such as `/usr/lib/libfoo.so`.
+    Posix,
+    /// Windows paths separated with '\\' such as `C:\Windows\foo.dll`.
+    Windows,
+}
+
+impl PathStyle {
+    /// Return the style of the OS where the program runs.
+    ///
+    /// ```
+    /// use path_slash::PathStyle;
+    ///
+    /// #[cfg(target_os = "windows")]
+    /// assert_eq!(PathStyle::native(), PathStyle::Windows);
+    /// #[cfg(not(target_os = "windows"))]
+    /// assert_eq!(PathStyle::native(), PathStyle::Posix);
+    /// ```
+    pub fn native() -> Self {
+        if cfg!(target_os = "windows") {
+            PathStyle::Windows
+        } else {
+            PathStyle::Posix
+        }
+    }
+
+    /// Return the main path separator of the style.
+    ///
+    /// ```
+    /// use path_slash::PathStyle;
+    ///
+    /// assert_eq!(PathStyle::Posix.separator(), '/');
+    /// assert_eq!(PathStyle::Windows.separator(), '\\');
+    /// ```
+    pub fn separator(self) -> char {
+        match self {
+            PathStyle::Posix => '/',
+            PathStyle::Windows => '\\',
+        }
+    }
+}
+
+/// Convert the slash path (path separated with '/') into a native path in the given style.
+///
+/// The result does not depend on the OS where the program runs. With [`PathStyle::native`],
+/// the result is the same as [`PathBufExt::from_slash`](crate::PathBufExt::from_slash).
+///
+/// ```
+/// use path_slash::{from_slash_as, PathStyle};
+///
+/// assert_eq!(from_slash_as("C:/foo/bar.dll", PathStyle::Windows), r"C:\foo\bar.dll");
+/// assert_eq!(from_slash_as("/foo/bar.so", PathStyle::Posix), "/foo/bar.so");
+/// ```
+pub fn from_slash_as(slash: &str, style: PathStyle) -> String {
+    match style {
+        PathStyle::Posix => posix::from_slash(slash).into_owned(),
+        PathStyle::Windows => windows::from_slash(slash).into_owned(),
+    }
+}
+
+/// Convert the native path in the given style into a slash path.
+///
+/// The result does not depend on the OS where the program runs. With [`PathStyle::native`],
+/// the result is the same as [`PathExt::to_slash`](crate::PathExt::to_slash).
+///
+/// ```
+/// use path_slash::{to_slash_from, PathStyle};
+///
+/// assert_eq!(to_slash_from(r"C:\foo\bar.dll", PathStyle::Windows), "C:/foo/bar.dll");
+/// assert_eq!(to_slash_from(r"foo\bar.so", PathStyle::Posix), r"foo\bar.so");
+/// ```
+pub fn to_slash_from(path: &str, style: PathStyle) -> String {
+    match style {
+        PathStyle::Posix => posix::to_slash(path).into_owned(),
+        PathStyle::Windows => windows::to_slash(path).into_owned(),
+    }
+}
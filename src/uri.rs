@@ -0,0 +1,346 @@
+//! Conversion between slash paths and `file://` URIs which works on any OS.
+//!
+//! File URIs are built from absolute slash paths as described in
+//! [RFC 8089](https://www.rfc-editor.org/rfc/rfc8089). Characters other than unreserved
+//! characters, sub-delimiters, `:`, `@` and `/` are percent-encoded as UTF-8 bytes.
+//!
+//! Paths are interpreted in the given [`PathStyle`] so that the result does not depend on the OS
+//! where the program runs. For Windows paths, drive letters are put in the path like
+//! `file:///C:/foo` and UNC paths `\\server\share\foo` are put in the authority like
+//! `file://server/share/foo`. [`PathExt::to_file_uri`](crate::PathExt::to_file_uri) and
+//! [`PathBufExt::from_file_uri`](crate::PathBufExt::from_file_uri) do the same conversion for
+//! native paths.
+//!
+//! ```
+//! use path_slash::uri;
+//! use path_slash::PathStyle;
+//!
+//! assert_eq!(
+//!     uri::to_file_uri("C:/foo/bar baz.txt", PathStyle::Windows).unwrap(),
+//!     "file:///C:/foo/bar%20baz.txt",
+//! );
+//! assert_eq!(
+//!     uri::from_file_uri("file://server/share/foo", PathStyle::Windows).unwrap(),
+//!     r"\\server\share/foo",
+//! );
+//! assert_eq!(
+//!     uri::from_file_uri("file:///usr/lib/%E3%81%82", PathStyle::Posix).unwrap(),
+//!     "/usr/lib/あ",
+//! );
+//! ```
+
+use crate::windows::{self, Prefix};
+use crate::{PathStyle, SlashPathBuf};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// An error returned when converting a path into a file URI or a file URI into a path fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileUriError {
+    /// The URI does not start with a scheme such as `file:`.
+    MissingScheme,
+    /// The scheme of the URI is not `file`. The scheme is contained.
+    UnsupportedScheme(String),
+    /// The host of the URI cannot be represented in the path style. Only Windows paths can have
+    /// hosts as UNC paths. The host is contained.
+    UnsupportedHost(String),
+    /// The path is not absolute. File URIs can only represent absolute paths.
+    NotAbsolute,
+    /// The path has a Windows path prefix which cannot be represented in file URIs such as
+    /// `\\?\pictures` or `\\.\COM1`.
+    UnsupportedPrefix,
+    /// The URI contains `%` which is not followed by two hexadecimal digits.
+    InvalidPercentEncoding,
+    /// The path contains non-Unicode sequence, or the percent-decoded URI is not valid UTF-8.
+    NonUnicode,
+    /// The URI contains a percent-encoded character which cannot appear in a path component
+    /// such as `/` (`%2F`), `\` (`%5C`) and NUL (`%00`). The decoded character is contained.
+    DisallowedEncodedChar(char),
+}
+
+impl fmt::Display for FileUriError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileUriError::MissingScheme => write!(f, "URI has no scheme"),
+            FileUriError::UnsupportedScheme(scheme) => {
+                write!(
+                    f,
+                    "scheme {:?} is not supported. only \"file\" is supported",
+                    scheme
+                )
+            }
+            FileUriError::UnsupportedHost(host) => {
+                write!(f, "host {:?} cannot be represented in path", host)
+            }
+            FileUriError::NotAbsolute => write!(f, "path is not absolute"),
+            FileUriError::UnsupportedPrefix => {
+                write!(f, "path prefix cannot be represented in file URI")
+            }
+            FileUriError::InvalidPercentEncoding => write!(f, "invalid percent-encoding"),
+            FileUriError::NonUnicode => write!(f, "path contains non-Unicode sequence"),
+            FileUriError::DisallowedEncodedChar(c) => {
+                write!(
+                    f,
+                    "percent-encoded character {:?} is not allowed in path",
+                    c
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FileUriError {}
+
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+// Characters which can be put in the path of URI as-is. They are unreserved characters,
+// sub-delimiters, ':', '@' and '/' (RFC 3986 section 3.3)
+fn is_path_char(b: u8) -> bool {
+    match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => true,
+        b'-' | b'.' | b'_' | b'~' => true,
+        b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' => true,
+        b':' | b'@' | b'/' => true,
+        _ => false,
+    }
+}
+
+fn percent_encode(s: &str, buf: &mut String) {
+    for &b in s.as_bytes() {
+        if is_path_char(b) {
+            buf.push(b as char);
+        } else {
+            buf.push('%');
+            buf.push(HEX[(b >> 4) as usize] as char);
+            buf.push(HEX[(b & 0xf) as usize] as char);
+        }
+    }
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+// Decode the percent-encoded string. Separators and NUL must not be decoded since they would
+// change the components of the path
+fn percent_decode(s: &str) -> Result<String, FileUriError> {
+    let bytes = s.as_bytes();
+    let mut buf = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hi = bytes.get(i + 1).cloned().and_then(hex_value);
+            let lo = bytes.get(i + 2).cloned().and_then(hex_value);
+            let b = match (hi, lo) {
+                (Some(hi), Some(lo)) => hi << 4 | lo,
+                _ => return Err(FileUriError::InvalidPercentEncoding),
+            };
+            if b == b'/' || b == b'\\' || b == b'\0' {
+                return Err(FileUriError::DisallowedEncodedChar(b as char));
+            }
+            buf.push(b);
+            i += 3;
+        } else {
+            buf.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(buf).map_err(|_| FileUriError::NonUnicode)
+}
+
+/// Convert the absolute slash path into a `file://` URI.
+///
+/// The path is interpreted in the given style. For [`PathStyle::Windows`], the path may also be
+/// separated with '\\'. Drive paths are converted into `file:///C:/...` and UNC paths are
+/// converted into `file://server/share/...`. Verbatim prefixes such as `\\?\C:` are converted
+/// in the same way as their non-verbatim forms.
+///
+/// Empty components and `.` components are removed as [`std::path::Components`] does, so the
+/// result is the same as [`PathExt::to_file_uri`](crate::PathExt::to_file_uri) for the same path
+/// on the OS of the style.
+///
+/// Relative paths such as `foo/bar`, `C:foo` and `/foo` on Windows are rejected with
+/// [`FileUriError::NotAbsolute`].
+///
+/// ```
+/// use path_slash::uri::{self, FileUriError};
+/// use path_slash::PathStyle;
+///
+/// assert_eq!(uri::to_file_uri("/usr/lib/a#b", PathStyle::Posix).unwrap(), "file:///usr/lib/a%23b");
+/// assert_eq!(uri::to_file_uri(r"C:\x\y z", PathStyle::Windows).unwrap(), "file:///C:/x/y%20z");
+/// assert_eq!(
+///     uri::to_file_uri(r"\\server\share\foo", PathStyle::Windows).unwrap(),
+///     "file://server/share/foo",
+/// );
+/// assert_eq!(uri::to_file_uri("foo/bar", PathStyle::Posix), Err(FileUriError::NotAbsolute));
+/// ```
+pub fn to_file_uri(slash: &str, style: PathStyle) -> Result<String, FileUriError> {
+    let mut buf = String::with_capacity(slash.len() + 8);
+    buf.push_str("file://");
+
+    let (rest, verbatim) = if style == PathStyle::Posix {
+        if !slash.starts_with('/') {
+            return Err(FileUriError::NotAbsolute);
+        }
+        (slash, false)
+    } else {
+        let (prefix, rest) = windows::split_prefix(slash);
+        let prefix = match prefix {
+            Some(prefix) => prefix,
+            None => return Err(FileUriError::NotAbsolute),
+        };
+        match prefix {
+            Prefix::Disk(drive) | Prefix::VerbatimDisk(drive) => {
+                if !rest.starts_with(|c| c == '/' || c == '\\') {
+                    return Err(FileUriError::NotAbsolute);
+                }
+                buf.push('/');
+                buf.push(drive as char);
+                buf.push(':');
+            }
+            Prefix::UNC(server, share) | Prefix::VerbatimUNC(server, share) => {
+                if server.is_empty() {
+                    return Err(FileUriError::UnsupportedPrefix);
+                }
+                percent_encode(server, &mut buf);
+                buf.push('/');
+                percent_encode(share, &mut buf);
+            }
+            Prefix::Verbatim(_) | Prefix::DeviceNS(_) => {
+                return Err(FileUriError::UnsupportedPrefix)
+            }
+        }
+        (rest, prefix.is_verbatim())
+    };
+
+    // Walk the components in the same way as `std::path::Components` so that the URI is the same
+    // as `PathExt::to_file_uri`, which calls this function with the native style. Empty and `.`
+    // components are skipped and a trailing separator is kept
+    let is_sep = |c: char| c == '/' || (style == PathStyle::Windows && c == '\\');
+    if rest.starts_with(is_sep) {
+        buf.push('/');
+    }
+    let mut has_component = false;
+    for comp in rest.split(is_sep) {
+        if comp.is_empty() || (comp == "." && !verbatim) {
+            continue;
+        }
+        if has_component {
+            buf.push('/');
+        }
+        percent_encode(comp, &mut buf);
+        has_component = true;
+    }
+    if has_component && rest.ends_with(is_sep) {
+        buf.push('/');
+    }
+    Ok(buf)
+}
+
+/// Convert the `file://` URI into a slash path.
+///
+/// The result is interpreted in the given style. The scheme is matched case-insensitively and
+/// an empty host or `localhost` means the local machine. For [`PathStyle::Windows`], a drive
+/// letter in the path is recognized even when `:` is percent-encoded as `%3A` or written as
+/// `|`, and a host is converted into a UNC path. Other styles reject hosts with
+/// [`FileUriError::UnsupportedHost`]. The query and the fragment of the URI are ignored.
+///
+/// Percent-encoded characters which would change the components of the path, such as `%2F`,
+/// `%5C` and `%00`, are rejected with [`FileUriError::DisallowedEncodedChar`]. `\` is rejected
+/// on any style since it cannot appear in a slash path except for a Windows path prefix.
+///
+/// ```
+/// use path_slash::uri::{self, FileUriError};
+/// use path_slash::PathStyle;
+///
+/// assert_eq!(uri::from_file_uri("file:///C:/x/y%20z", PathStyle::Windows).unwrap(), "C:/x/y z");
+/// assert_eq!(uri::from_file_uri("file:///c%3A/x", PathStyle::Windows).unwrap(), "C:/x");
+/// assert_eq!(uri::from_file_uri("file://localhost/etc/hosts", PathStyle::Posix).unwrap(), "/etc/hosts");
+/// assert_eq!(
+///     uri::from_file_uri("https://example.com/", PathStyle::Posix),
+///     Err(FileUriError::UnsupportedScheme("https".to_string())),
+/// );
+/// ```
+pub fn from_file_uri(uri: &str, style: PathStyle) -> Result<SlashPathBuf, FileUriError> {
+    let colon = match uri.find(':') {
+        Some(i) if i > 0 => i,
+        _ => return Err(FileUriError::MissingScheme),
+    };
+    let scheme = &uri[..colon];
+    let is_scheme_char = |c: char| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.';
+    if !scheme.starts_with(|c: char| c.is_ascii_alphabetic()) || !scheme.chars().all(is_scheme_char)
+    {
+        return Err(FileUriError::MissingScheme);
+    }
+    if !scheme.eq_ignore_ascii_case("file") {
+        return Err(FileUriError::UnsupportedScheme(scheme.to_string()));
+    }
+
+    let rest = &uri[colon + 1..];
+    let rest = match rest.find(|c| c == '?' || c == '#') {
+        Some(i) => &rest[..i],
+        None => rest,
+    };
+    let (host, path) = if rest.starts_with("//") {
+        let rest = &rest[2..];
+        match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, ""),
+        }
+    } else {
+        ("", rest)
+    };
+    let host = percent_decode(host)?;
+    let path = percent_decode(path)?;
+    let is_local = host.is_empty() || host.eq_ignore_ascii_case("localhost");
+
+    if style == PathStyle::Posix {
+        if !is_local {
+            return Err(FileUriError::UnsupportedHost(host));
+        }
+        if !path.starts_with('/') {
+            return Err(FileUriError::NotAbsolute);
+        }
+        return Ok(SlashPathBuf::from(path));
+    }
+
+    if !is_local {
+        // UNC path. The first component of the path is the share name
+        let path = if path.is_empty() { &path } else { &path[1..] };
+        if path.is_empty() || path.starts_with('/') {
+            return Err(FileUriError::UnsupportedHost(host));
+        }
+        let (share, rest) = match path.find('/') {
+            Some(i) => (&path[..i], &path[i..]),
+            None => (path, ""),
+        };
+        return Ok(SlashPathBuf::from(format!(r"\\{}\{}{}", host, share, rest)));
+    }
+
+    // Drive path such as `/C:/foo` or `/C|/foo`
+    let b = path.as_bytes();
+    let is_drive = b.len() >= 3
+        && b[0] == b'/'
+        && b[1].is_ascii_alphabetic()
+        && (b[2] == b':' || b[2] == b'|')
+        && b.get(3).map_or(true, |&c| c == b'/');
+    if !is_drive {
+        return Err(FileUriError::NotAbsolute);
+    }
+    let mut slash = String::with_capacity(path.len());
+    slash.push(b[1].to_ascii_uppercase() as char);
+    slash.push(':');
+    match &path[3..] {
+        "" => slash.push('/'),
+        rest => slash.push_str(rest),
+    }
+    Ok(SlashPathBuf::from(slash))
+}
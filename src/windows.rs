@@ -0,0 +1,508 @@
+//! Conversion between Windows paths and slash paths which works on any OS.
+//!
+//! [`PathExt`](crate::PathExt) and the other traits rely on [`std::path`], which parses drive
+//! letters and UNC prefixes only when compiled for Windows. The functions in this module parse
+//! Windows paths as plain strings instead, so they behave in the same way on every OS.
+//!
+//! ```
+//! use path_slash::windows;
+//!
+//! assert_eq!(windows::to_slash(r"C:\Users\x\file.txt"), "C:/Users/x/file.txt");
+//! assert_eq!(windows::to_slash(r"\\server\share\a"), r"\\server\share/a");
+//! assert_eq!(windows::from_slash("C:/Users/x/file.txt"), r"C:\Users\x\file.txt");
+//! ```
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(all(feature = "std", target_os = "windows"))]
+use std::path::{Path, MAIN_SEPARATOR};
+
+// Workaround for Windows. There is no way to extract raw byte sequence from `OsStr` (in `Path`).
+// And `OsStr::to_string_lossy` may cause extra heap allocation.
+#[cfg(all(feature = "std", target_os = "windows"))]
+pub(crate) fn ends_with_main_sep(p: &Path) -> bool {
+    use std::os::windows::ffi::OsStrExt as _;
+    p.as_os_str().encode_wide().last() == Some(MAIN_SEPARATOR as u16)
+}
+
+/// Encode the UTF-16 sequence into [WTF-8](https://simonsapin.github.io/wtf-8/) bytes.
+///
+/// WTF-8 is a superset of UTF-8 which can also represent unpaired surrogates. This is the same
+/// encoding as [`OsStr`](std::ffi::OsStr) uses internally on Windows. Unlike
+/// [`String::from_utf16_lossy`], this conversion never loses information.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// assert_eq!(windows::wide_to_wtf8(&[0x61, 0x2f, 0x62]), b"a/b");
+/// assert_eq!(windows::wide_to_wtf8(&[0x61, 0xd800]), b"a\xed\xa0\x80");
+/// ```
+pub fn wide_to_wtf8(wide: &[u16]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(wide.len());
+    for c in core::char::decode_utf16(wide.iter().cloned()) {
+        match c {
+            Ok(c) => {
+                let mut b = [0; 4];
+                buf.extend_from_slice(c.encode_utf8(&mut b).as_bytes());
+            }
+            Err(e) => {
+                // Unpaired surrogates are encoded in the same way as UTF-8 encodes code points
+                let u = e.unpaired_surrogate();
+                buf.push(0xe0 | (u >> 12) as u8);
+                buf.push(0x80 | ((u >> 6) & 0x3f) as u8);
+                buf.push(0x80 | (u & 0x3f) as u8);
+            }
+        }
+    }
+    buf
+}
+
+/// Decode the [WTF-8](https://simonsapin.github.io/wtf-8/) bytes into a UTF-16 sequence. This
+/// is the reverse conversion of [`wide_to_wtf8`].
+///
+/// Returns `None` when the bytes are not valid WTF-8. For example, when they contain an invalid
+/// UTF-8 sequence or a surrogate pair encoded as two separate surrogates.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// assert_eq!(windows::wtf8_to_wide(b"a/b"), Some(vec![0x61, 0x2f, 0x62]));
+/// assert_eq!(windows::wtf8_to_wide(b"a\xed\xa0\x80"), Some(vec![0x61, 0xd800]));
+/// assert_eq!(windows::wtf8_to_wide(b"a\xff"), None);
+/// ```
+pub fn wtf8_to_wide(wtf8: &[u8]) -> Option<Vec<u16>> {
+    let mut buf = Vec::with_capacity(wtf8.len());
+    let mut prev_lead_surrogate = false;
+    let mut i = 0;
+    while i < wtf8.len() {
+        let b = wtf8[i];
+        let (mut cp, len, min) = match b {
+            0x00..=0x7f => (u32::from(b), 1, 0),
+            0xc0..=0xdf => (u32::from(b & 0x1f), 2, 0x80),
+            0xe0..=0xef => (u32::from(b & 0x0f), 3, 0x800),
+            0xf0..=0xf7 => (u32::from(b & 0x07), 4, 0x10000),
+            _ => return None,
+        };
+        let cont = wtf8.get(i + 1..i + len)?;
+        for &c in cont {
+            if c & 0xc0 != 0x80 {
+                return None;
+            }
+            cp = (cp << 6) | u32::from(c & 0x3f);
+        }
+        if cp < min || cp > 0x10ffff {
+            return None; // Overlong encoding or out of range
+        }
+
+        let is_trail_surrogate = (0xdc00..=0xdfff).contains(&cp);
+        if prev_lead_surrogate && is_trail_surrogate {
+            return None; // Surrogate pair must be encoded as a single code point
+        }
+        prev_lead_surrogate = (0xd800..=0xdbff).contains(&cp);
+
+        if cp >= 0x10000 {
+            let cp = cp - 0x10000;
+            buf.push(0xd800 | (cp >> 10) as u16);
+            buf.push(0xdc00 | (cp & 0x3ff) as u16);
+        } else {
+            buf.push(cp as u16);
+        }
+        i += len;
+    }
+    Some(buf)
+}
+
+fn is_sep(b: u8) -> bool {
+    b == b'\\' || b == b'/'
+}
+
+fn is_verbatim_sep(b: u8) -> bool {
+    b == b'\\'
+}
+
+// Prefix of a Windows path parsed from a byte sequence. This is the same as `std::path::Prefix`
+// except that the parts are slices of the original path.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum RawPrefix<'a> {
+    Verbatim(&'a [u8]),
+    VerbatimUNC(&'a [u8], &'a [u8]),
+    VerbatimDisk,
+    DeviceNS(&'a [u8]),
+    UNC(&'a [u8], &'a [u8]),
+    Disk,
+}
+
+impl<'a> RawPrefix<'a> {
+    pub(crate) fn len(self) -> usize {
+        use RawPrefix::*;
+        match self {
+            Verbatim(x) => 4 + x.len(),
+            VerbatimUNC(x, y) => 8 + x.len() + if y.is_empty() { 0 } else { 1 + y.len() },
+            VerbatimDisk => 6,
+            UNC(x, y) => 2 + x.len() + if y.is_empty() { 0 } else { 1 + y.len() },
+            DeviceNS(x) => 4 + x.len(),
+            Disk => 2,
+        }
+    }
+
+    pub(crate) fn is_verbatim(self) -> bool {
+        match self {
+            RawPrefix::Verbatim(_) | RawPrefix::VerbatimUNC(..) | RawPrefix::VerbatimDisk => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn has_implicit_root(self) -> bool {
+        match self {
+            RawPrefix::Disk => false,
+            _ => true,
+        }
+    }
+}
+
+/// Windows path prefix parsed by [`parse_prefix`]. This is the same as [`std::path::Prefix`]
+/// except that it is available on any OS and the parts are string slices of the original path.
+///
+/// ```
+/// use path_slash::windows::{self, Prefix};
+///
+/// assert_eq!(windows::parse_prefix(r"C:\foo"), Some(Prefix::Disk(b'C')));
+/// assert_eq!(windows::parse_prefix(r"\\server\share/foo"), Some(Prefix::UNC("server", "share")));
+/// assert_eq!(windows::parse_prefix("foo/bar"), None);
+/// ```
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Prefix<'a> {
+    /// Verbatim prefix like `\\?\cat_pics`. The part after `\\?\` is stored.
+    Verbatim(&'a str),
+    /// Verbatim UNC prefix like `\\?\UNC\server\share`. The server name and the share name
+    /// are stored.
+    VerbatimUNC(&'a str, &'a str),
+    /// Verbatim disk prefix like `\\?\C:`. The uppercase drive letter is stored.
+    VerbatimDisk(u8),
+    /// Device namespace prefix like `\\.\COM42`. The device name is stored.
+    DeviceNS(&'a str),
+    /// UNC prefix like `\\server\share`. The server name and the share name are stored.
+    UNC(&'a str, &'a str),
+    /// Disk prefix like `C:`. The uppercase drive letter is stored.
+    Disk(u8),
+}
+
+impl<'a> Prefix<'a> {
+    /// Return `true` when the prefix is verbatim, i.e. it begins with `\\?\`.
+    ///
+    /// ```
+    /// use path_slash::windows::Prefix;
+    ///
+    /// assert!(Prefix::VerbatimDisk(b'C').is_verbatim());
+    /// assert!(!Prefix::Disk(b'C').is_verbatim());
+    /// ```
+    pub fn is_verbatim(&self) -> bool {
+        match self {
+            Prefix::Verbatim(_) | Prefix::VerbatimUNC(..) | Prefix::VerbatimDisk(_) => true,
+            _ => false,
+        }
+    }
+
+    fn from_raw(path: &'a str, raw: RawPrefix<'_>) -> Self {
+        // Parts of the raw prefix are slices of `path` split at ASCII characters
+        let part = |b: &[u8]| {
+            let start = b.as_ptr() as usize - path.as_ptr() as usize;
+            &path[start..start + b.len()]
+        };
+        let bytes = path.as_bytes();
+        match raw {
+            RawPrefix::Verbatim(x) => Prefix::Verbatim(part(x)),
+            RawPrefix::VerbatimUNC(x, y) => Prefix::VerbatimUNC(part(x), part(y)),
+            RawPrefix::VerbatimDisk => Prefix::VerbatimDisk(bytes[4].to_ascii_uppercase()),
+            RawPrefix::DeviceNS(x) => Prefix::DeviceNS(part(x)),
+            RawPrefix::UNC(x, y) => Prefix::UNC(part(x), part(y)),
+            RawPrefix::Disk => Prefix::Disk(bytes[0].to_ascii_uppercase()),
+        }
+    }
+}
+
+/// Parse the Windows path prefix at the start of the path. This works on any OS.
+///
+/// The path can be either a Windows path separated with `\` or a slash path converted by
+/// [`to_slash`]. Prefixes are parsed in the same way as [`std::path::Path::components`] on
+/// Windows except that `/` also separates the parts of verbatim prefixes since slash paths use
+/// `/` as separator. Returns `None` when the path has no prefix.
+///
+/// ```
+/// use path_slash::windows::{self, Prefix};
+///
+/// assert_eq!(windows::parse_prefix("c:/foo"), Some(Prefix::Disk(b'C')));
+/// assert_eq!(windows::parse_prefix(r"\\?\C:\foo"), Some(Prefix::VerbatimDisk(b'C')));
+/// assert_eq!(
+///     windows::parse_prefix(r"\\?\UNC\server\share/foo"),
+///     Some(Prefix::VerbatimUNC("server", "share")),
+/// );
+/// assert_eq!(windows::parse_prefix(r"\\.\COM42\foo"), Some(Prefix::DeviceNS("COM42")));
+/// assert_eq!(windows::parse_prefix("/foo"), None);
+/// ```
+pub fn parse_prefix(path: &str) -> Option<Prefix<'_>> {
+    split_prefix(path).0
+}
+
+/// Split the path into the Windows path prefix and the rest. This works on any OS.
+///
+/// The prefix is parsed in the same way as [`parse_prefix`]. When the path has no prefix, the
+/// whole path is returned as the rest.
+///
+/// ```
+/// use path_slash::windows::{self, Prefix};
+///
+/// assert_eq!(windows::split_prefix("C:/foo"), (Some(Prefix::Disk(b'C')), "/foo"));
+/// assert_eq!(
+///     windows::split_prefix(r"\\server\share\foo"),
+///     (Some(Prefix::UNC("server", "share")), r"\foo"),
+/// );
+/// assert_eq!(windows::split_prefix("foo/bar"), (None, "foo/bar"));
+/// ```
+pub fn split_prefix(path: &str) -> (Option<Prefix<'_>>, &str) {
+    match parse_prefix_bytes(path.as_bytes(), true) {
+        Some(raw) => (Some(Prefix::from_raw(path, raw)), &path[raw.len()..]),
+        None => (None, path),
+    }
+}
+
+// Split the path at the next separator. The separator itself is not included in both sides.
+// Both sides are always subslices of `path` so that `Prefix::from_raw` can locate them.
+fn next_component(path: &[u8], verbatim: bool) -> (&[u8], &[u8]) {
+    let sep = if verbatim { is_verbatim_sep } else { is_sep };
+    match path.iter().position(|&b| sep(b)) {
+        Some(i) => (&path[..i], &path[i + 1..]),
+        None => (path, &path[path.len()..]),
+    }
+}
+
+fn is_drive(path: &[u8]) -> bool {
+    path.len() >= 2 && path[0].is_ascii_alphabetic() && path[1] == b':'
+}
+
+fn is_drive_exact(path: &[u8]) -> bool {
+    path.get(2).map_or(true, |&b| is_sep(b)) && is_drive(path)
+}
+
+// Parse the prefix in the same way as `std::path` on Windows. The leading `\\?\`, `\\.\` and
+// `\\` markers accept both `\` and `/`, but the parts of verbatim prefixes are only separated
+// by `\`. When `lenient` is true, the parts of verbatim prefixes are also separated by `/` since
+// `/` is always a separator in slash paths.
+pub(crate) fn parse_prefix_bytes(path: &[u8], lenient: bool) -> Option<RawPrefix<'_>> {
+    let verbatim = !lenient;
+    if path.len() < 2 || !is_sep(path[0]) || !is_sep(path[1]) {
+        return if is_drive(path) {
+            Some(RawPrefix::Disk)
+        } else {
+            None
+        };
+    }
+
+    let path = &path[2..];
+    if path.len() >= 2 && path[0] == b'?' && is_sep(path[1]) {
+        let path = &path[2..];
+        if path.len() >= 4 && &path[..3] == b"UNC" && is_sep(path[3]) {
+            let (server, rest) = next_component(&path[4..], verbatim);
+            let (share, _) = next_component(rest, verbatim);
+            Some(RawPrefix::VerbatimUNC(server, share))
+        } else {
+            let (prefix, _) = next_component(path, verbatim);
+            Some(if is_drive_exact(prefix) {
+                RawPrefix::VerbatimDisk
+            } else {
+                RawPrefix::Verbatim(prefix)
+            })
+        }
+    } else if path.len() >= 2 && path[0] == b'.' && is_sep(path[1]) {
+        let (device, _) = next_component(&path[2..], false);
+        Some(RawPrefix::DeviceNS(device))
+    } else {
+        let (server, rest) = next_component(path, false);
+        let (share, _) = next_component(rest, false);
+        if !server.is_empty() && !share.is_empty() {
+            Some(RawPrefix::UNC(server, share))
+        } else {
+            None
+        }
+    }
+}
+
+// Convert the Windows path into a slash path in the same way as `PathExt::to_slash` on Windows.
+// This walks the components like `std::path::Components` does.
+fn to_slash_bytes(path: &[u8]) -> Vec<u8> {
+    let prefix = parse_prefix_bytes(path, false);
+    let prefix_len = prefix.map_or(0, RawPrefix::len);
+    let verbatim = prefix.map_or(false, RawPrefix::is_verbatim);
+    let sep = if verbatim { is_verbatim_sep } else { is_sep };
+
+    let mut buf = Vec::with_capacity(path.len());
+    buf.extend_from_slice(&path[..prefix_len]);
+    // C:\foo is [Prefix, RootDir, Normal]. A prefix is not followed by '/' to avoid C://
+
+    let mut body = &path[prefix_len..];
+    let has_physical_root = body.first().map_or(false, |&b| is_sep(b));
+    let has_root = has_physical_root || prefix.map_or(false, RawPrefix::has_implicit_root);
+    if has_physical_root {
+        body = &body[1..];
+        buf.push(b'/');
+    } else if has_root {
+        if !verbatim {
+            buf.push(b'/');
+        }
+    } else if prefix.is_none()
+        && body.first() == Some(&b'.')
+        && body.get(1).map_or(true, |&b| sep(b))
+    {
+        // `std::path::Components` yields `CurDir` only when the path has no prefix. `C:.\foo` is
+        // [Prefix, Normal]
+        body = &body[1..];
+        buf.extend_from_slice(b"./");
+    }
+
+    for comp in body.split(|&b| sep(b)) {
+        match comp {
+            b"" => continue,
+            b"." if !verbatim => continue,
+            _ => buf.extend_from_slice(comp),
+        }
+        buf.push(b'/');
+    }
+
+    if path.last() != Some(&b'\\') && buf != b"/" && buf.last() == Some(&b'/') {
+        buf.pop(); // Pop last '/'
+    }
+
+    buf
+}
+
+/// Convert the Windows path into a slash path.
+///
+/// The result is the same as [`PathExt::to_slash`](crate::PathExt::to_slash) on Windows. Drive
+/// letters (`C:`), UNC prefixes (`\\server\share`), verbatim prefixes (`\\?\`) and device
+/// prefixes (`\\.\`) are preserved as they are. Only when the path is changed, heap allocation
+/// happens and `Cow::Owned` is returned.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// assert_eq!(windows::to_slash(r"foo\bar\piyo.txt"), "foo/bar/piyo.txt");
+/// assert_eq!(windows::to_slash(r"C:\foo\bar"), "C:/foo/bar");
+/// assert_eq!(windows::to_slash(r"\\?\C:\foo\bar"), r"\\?\C:/foo/bar");
+/// assert_eq!(windows::to_slash(r"\\server\share"), r"\\server\share");
+/// ```
+pub fn to_slash(path: &str) -> Cow<'_, str> {
+    let buf = to_slash_bytes(path.as_bytes());
+    if buf == path.as_bytes() {
+        return Cow::Borrowed(path);
+    }
+    // The path is only split at ASCII characters so the result is always valid UTF-8
+    Cow::Owned(String::from_utf8(buf).expect("slash path is valid UTF-8"))
+}
+
+/// Convert the Windows path encoded in [WTF-8](https://simonsapin.github.io/wtf-8/) into a
+/// slash path in WTF-8.
+///
+/// This is the same as [`to_slash`] but works on WTF-8 bytes. Since WTF-8 can represent unpaired
+/// surrogates, this conversion is lossless for any Windows path. Only when the path is changed,
+/// heap allocation happens and `Cow::Owned` is returned.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// assert_eq!(windows::to_slash_wtf8(b"C:\\foo\\\xed\xa0\x80"), &b"C:/foo/\xed\xa0\x80"[..]);
+/// ```
+pub fn to_slash_wtf8(path: &[u8]) -> Cow<'_, [u8]> {
+    let buf = to_slash_bytes(path);
+    if buf == path {
+        Cow::Borrowed(path)
+    } else {
+        Cow::Owned(buf)
+    }
+}
+
+/// Convert the Windows path encoded in UTF-16 into a slash path in UTF-16.
+///
+/// This is the same as [`to_slash`] but works on UTF-16 sequence such as the result of
+/// [`OsStrExt::encode_wide`](https://doc.rust-lang.org/std/os/windows/ffi/trait.OsStrExt.html#tymethod.encode_wide).
+/// Unpaired surrogates are kept as they are so this conversion is lossless for any Windows path.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// let path = [0x43, 0x3a, 0x5c, 0x61, 0x5c, 0xd800]; // C:\a\<unpaired surrogate>
+/// assert_eq!(windows::to_slash_wide(&path), [0x43, 0x3a, 0x2f, 0x61, 0x2f, 0xd800]);
+/// ```
+pub fn to_slash_wide(path: &[u16]) -> Vec<u16> {
+    let buf = to_slash_bytes(&wide_to_wtf8(path));
+    // The path is only split at ASCII characters so the result is always valid WTF-8
+    wtf8_to_wide(&buf).expect("slash path is valid WTF-8")
+}
+
+/// Convert the slash path (path separated with '/') into a Windows path.
+///
+/// Any '/' in the slash path is replaced with '\\'. The result is the same as
+/// [`PathBufExt::from_slash`](crate::PathBufExt::from_slash) on Windows. Only when the
+/// replacement happens, heap allocation happens and `Cow::Owned` is returned.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// assert_eq!(windows::from_slash("foo/bar/piyo.txt"), r"foo\bar\piyo.txt");
+/// assert_eq!(windows::from_slash("C:/foo/bar"), r"C:\foo\bar");
+/// ```
+pub fn from_slash(slash: &str) -> Cow<'_, str> {
+    crate::replace_sep(slash, '/', '\\')
+}
+
+/// Convert the slash path encoded in [WTF-8](https://simonsapin.github.io/wtf-8/) into a Windows
+/// path in WTF-8.
+///
+/// This is the same as [`from_slash`] but works on WTF-8 bytes. Only when the replacement
+/// happens, heap allocation happens and `Cow::Owned` is returned.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// assert_eq!(windows::from_slash_wtf8(b"C:/foo/\xed\xa0\x80"), &b"C:\\foo\\\xed\xa0\x80"[..]);
+/// ```
+pub fn from_slash_wtf8(slash: &[u8]) -> Cow<'_, [u8]> {
+    if slash.contains(&b'/') {
+        Cow::Owned(
+            slash
+                .iter()
+                .map(|&b| if b == b'/' { b'\\' } else { b })
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(slash)
+    }
+}
+
+/// Convert the slash path encoded in UTF-16 into a Windows path in UTF-16.
+///
+/// This is the same as [`from_slash`] but works on UTF-16 sequence. The result can be converted
+/// into [`OsString`](std::ffi::OsString) with
+/// [`OsStringExt::from_wide`](https://doc.rust-lang.org/std/os/windows/ffi/trait.OsStringExt.html#tymethod.from_wide)
+/// on Windows.
+///
+/// ```
+/// use path_slash::windows;
+///
+/// let slash = [0x43, 0x3a, 0x2f, 0x61, 0x2f, 0xd800]; // C:/a/<unpaired surrogate>
+/// assert_eq!(windows::from_slash_wide(&slash), [0x43, 0x3a, 0x5c, 0x61, 0x5c, 0xd800]);
+/// ```
+pub fn from_slash_wide(slash: &[u16]) -> Vec<u16> {
+    slash
+        .iter()
+        .map(|&u| {
+            if u == u16::from(b'/') {
+                u16::from(b'\\')
+            } else {
+                u
+            }
+        })
+        .collect()
+}
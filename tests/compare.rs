@@ -0,0 +1,128 @@
+use path_slash::compare::{CaseFoldEq, Equivalence, SlashPathKey};
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
+
+fn hash_of<E: Equivalence>(path: &str) -> u64 {
+    let mut h = DefaultHasher::new();
+    E::hash_path(path, &mut h);
+    h.finish()
+}
+
+#[test]
+fn case_fold_eq() {
+    for (lhs, rhs, expected) in &[
+        ("", "", true),
+        ("foo/bar.txt", "foo/bar.txt", true),
+        ("Foo/Bar.TXT", "foo/bar.txt", true),
+        ("C:/Users", "c:/users", true),
+        ("ÄÖÜ/Σ", "äöü/σ", true),
+        ("ǅ", "ǆ", true),
+        ("ſ/K/Å", "s/k/å", true),
+        ("ΣΑΣ", "σας", true),
+        ("ς", "σ", true),
+        ("ẞ", "ß", true),
+        ("ß", "SS", false),
+        ("ß", "ss", false),
+        ("ı", "i", false),
+        ("İ", "i", false),
+        ("foo/bar", "foo/baz", false),
+        ("foo/bar", "foo//bar", false),
+        ("foo/bar/", "foo/bar", false),
+    ] {
+        assert_eq!(
+            CaseFoldEq::equivalent(lhs, rhs),
+            *expected,
+            "{:?}",
+            (lhs, rhs)
+        );
+        if *expected {
+            assert_eq!(
+                hash_of::<CaseFoldEq>(lhs),
+                hash_of::<CaseFoldEq>(rhs),
+                "{:?}",
+                (lhs, rhs)
+            );
+        }
+    }
+}
+
+#[test]
+fn case_fold_normalize_borrowed() {
+    for path in &["", "foo/bar.txt", "あ/い", "123/_-.", "ß", "σασ"] {
+        assert_eq!(CaseFoldEq::normalize(path), Cow::Borrowed(*path));
+    }
+    assert_eq!(
+        CaseFoldEq::normalize("Foo"),
+        Cow::<str>::Owned("foo".to_string())
+    );
+    for (path, expected) in &[
+        ("ſ", "s"),
+        ("ς", "σ"),
+        ("ΣΑΣ", "σασ"),
+        ("ẞ", "ß"),
+        ("K", "k"),
+    ] {
+        assert_eq!(CaseFoldEq::normalize(path), *expected, "{:?}", path);
+    }
+}
+
+#[test]
+fn slash_path_key() {
+    let mut set = HashSet::new();
+    assert!(set.insert(SlashPathKey::<CaseFoldEq>::new("src/Lib.rs")));
+    assert!(!set.insert(SlashPathKey::new("SRC/lib.RS")));
+    assert!(set.insert(SlashPathKey::new("src/main.rs")));
+    assert_eq!(set.len(), 2);
+
+    let mut map = HashMap::new();
+    map.insert(SlashPathKey::<CaseFoldEq>::new("README.md"), 1);
+    *map.entry(SlashPathKey::new("readme.md")).or_insert(0) += 1;
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[&SlashPathKey::new("ReadMe.MD")], 2);
+
+    let key = SlashPathKey::<CaseFoldEq>::new("Foo/Bar");
+    assert_eq!(key.as_slash_path(), "Foo/Bar");
+    assert_eq!(key.normalized(), "foo/bar");
+    assert_eq!(key.to_string(), "Foo/Bar");
+    assert_eq!(key.clone(), key);
+    assert_eq!(key.into_slash_path_buf(), "Foo/Bar");
+}
+
+#[cfg(feature = "unicode-normalization")]
+mod nfc {
+    use super::hash_of;
+    use path_slash::compare::{CaseFoldEq, Equivalence, NfcEq, SlashPathKey};
+    use std::borrow::Cow;
+    use std::collections::HashSet;
+
+    #[test]
+    fn nfc_eq() {
+        for (lhs, rhs, expected) in &[
+            ("foo/bar", "foo/bar", true),
+            ("caf\u{e9}", "cafe\u{301}", true),
+            ("\u{30ac}/x", "\u{30ab}\u{3099}/x", true),
+            ("\u{212b}", "\u{c5}", true),
+            ("Caf\u{e9}", "cafe\u{301}", false),
+            ("cafe", "caf\u{e9}", false),
+        ] {
+            assert_eq!(NfcEq::equivalent(lhs, rhs), *expected, "{:?}", (lhs, rhs));
+            if *expected {
+                assert_eq!(hash_of::<NfcEq>(lhs), hash_of::<NfcEq>(rhs));
+            }
+        }
+        assert_eq!(NfcEq::normalize("caf\u{e9}"), Cow::Borrowed("caf\u{e9}"));
+    }
+
+    #[test]
+    fn case_fold_and_nfc_eq() {
+        type MacEq = (CaseFoldEq, NfcEq);
+        assert!(MacEq::equivalent("Caf\u{e9}/A.txt", "cafe\u{301}/a.TXT"));
+        assert!(!MacEq::equivalent("Caf\u{e9}/A.txt", "cafe/a.txt"));
+
+        let mut set = HashSet::new();
+        assert!(set.insert(SlashPathKey::<MacEq>::new("Caf\u{e9}")));
+        assert!(!set.insert(SlashPathKey::new("CAFE\u{301}")));
+    }
+}
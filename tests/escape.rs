@@ -0,0 +1,84 @@
+use path_slash::escape::{BackslashConverter, EscapeStyle};
+use std::borrow::Cow;
+
+#[test]
+fn shell() {
+    let conv = BackslashConverter::new(EscapeStyle::Shell);
+    assert_eq!(conv.style(), EscapeStyle::Shell);
+    for (input, expected) in &[
+        ("", ""),
+        ("foo/bar", "foo/bar"),
+        (r"foo\bar", "foo/bar"),
+        (r"foo\ bar", "foo bar"),
+        (r"My\ Documents\a\ b\c", "My Documents/a b/c"),
+        (r"foo\\bar", "foo/bar"),
+        (r"a\'b\'\$HOME\`x\`", "a'b'$HOME`x`"),
+        (
+            r"\(\)\[\]\{\}\*\?\!\#\~\=\%\&\|\;\<\>",
+            "()[]{}*?!#~=%&|;<>",
+        ),
+        ("a\\\tb", "a\tb"),
+        (r"C:\Program\ Files\foo", "C:/Program Files/foo"),
+        (r"foo\", "foo/"),
+        (r"\foo", "/foo"),
+        (r"あ\ い\う", "あ い/う"),
+    ] {
+        assert_eq!(conv.to_slash(input), *expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn make() {
+    let conv = BackslashConverter::new(EscapeStyle::Make);
+    for (input, expected) in &[
+        (r"src\main.c", "src/main.c"),
+        (r"src\my\ file.c", "src/my file.c"),
+        (r"100\%\ done", "100% done"),
+        (r"\#not-comment\:x", "#not-comment:x"),
+        (r"a\(b\)", "a/(b/)"),
+        (r"a\\b", "a/b"),
+    ] {
+        assert_eq!(conv.to_slash(input), *expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn c_string() {
+    let conv = BackslashConverter::new(EscapeStyle::CString);
+    for (input, expected) in &[
+        (r"C:\\Users\\foo", "C:/Users/foo"),
+        (r"a\nb\tc\rd", "a\nb\tc\rd"),
+        (r#"\"quoted\"\'\?"#, "\"quoted\"'?"),
+        (r"\a\b\f\v", "\x07\x08\x0c\x0b"),
+        (r"\x41\x4a\x4A", "AJJ"),
+        (r"\x4", "\x04"),
+        (r"\x414", "A4"),
+        (r"\xe3\x81\x82", "あ"),
+        (r"\101\60\0", "A0\0"),
+        (r"\1010", "A0"),
+        (r"\xff", "\u{FFFD}"),
+        (r"\xg", "/xg"),
+        (r"\400", "/400"),
+        (r"foo\q", "foo/q"),
+        (r"foo\", "foo/"),
+    ] {
+        assert_eq!(conv.to_slash(input), *expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn borrowed() {
+    for style in &[EscapeStyle::Shell, EscapeStyle::Make, EscapeStyle::CString] {
+        let conv = BackslashConverter::new(*style);
+        assert_eq!(conv.to_slash("foo/bar baz"), Cow::Borrowed("foo/bar baz"));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn to_path_buf() {
+    let conv = BackslashConverter::new(EscapeStyle::Shell);
+    let p = conv.to_path_buf(r"foo\bar\ baz");
+    let expected = ["foo", "bar baz"].iter().collect::<std::path::PathBuf>();
+    assert_eq!(p, expected);
+}
@@ -0,0 +1,199 @@
+use path_slash::glob::{GlobErrorKind, SlashGlob};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+const MATCH_TESTS: &[(&str, &str, bool)] = &[
+    ("", "", true),
+    ("", "a", false),
+    ("abc", "abc", true),
+    ("abc", "abd", false),
+    ("abc", "ab", false),
+    ("abc", "abcd", false),
+    ("a/b", "a/b", true),
+    ("a/b", "a//b", false),
+    // ?
+    ("a?c", "abc", true),
+    ("a?c", "ac", false),
+    ("a?c", "a/c", false),
+    ("???", "あいう", true),
+    // *
+    ("*", "", true),
+    ("*", "abc", true),
+    ("*", "a/b", false),
+    ("*.rs", "lib.rs", true),
+    ("*.rs", ".rs", true),
+    ("*.rs", "src/lib.rs", false),
+    ("a*b*c", "aXbYc", true),
+    ("a*b*c", "abc", true),
+    ("a*b*c", "aXbY", false),
+    ("src/*", "src/lib.rs", true),
+    ("src/*", "src/", true),
+    ("src/*", "src/foo/lib.rs", false),
+    ("*/*", "src/lib.rs", true),
+    ("*/*", "lib.rs", false),
+    // **
+    ("**", "", true),
+    ("**", "a", true),
+    ("**", "a/b/c", true),
+    ("**/*.rs", "lib.rs", true),
+    ("**/*.rs", "src/lib.rs", true),
+    ("**/*.rs", "src/a/b/lib.rs", true),
+    ("**/*.rs", "src/a/b/lib.rs.bak", false),
+    ("src/**", "src/a", true),
+    ("src/**", "src/a/b", true),
+    ("src/**", "src", false),
+    ("src/**", "srcx/a", false),
+    ("src/**/lib.rs", "src/lib.rs", true),
+    ("src/**/lib.rs", "src/a/lib.rs", true),
+    ("src/**/lib.rs", "src/a/b/lib.rs", true),
+    ("src/**/lib.rs", "src/a/b/xlib.rs", false),
+    ("src/**/lib.rs", "tests/lib.rs", false),
+    ("**/a/**", "a/b", true),
+    ("**/a/**", "x/y/a/b/c", true),
+    ("**/a/**", "x/y/ab/c", false),
+    ("a/**/**/b", "a/b", true),
+    ("a/**/**/b", "a/x/y/b", true),
+    // [...]
+    ("[abc]", "a", true),
+    ("[abc]", "d", false),
+    ("[abc]", "", false),
+    ("[a-c]x", "bx", true),
+    ("[a-c]x", "dx", false),
+    ("[!a-c]x", "dx", true),
+    ("[!a-c]x", "bx", false),
+    ("[^a-c]x", "dx", true),
+    ("[!a]", "/", false),
+    ("[]]", "]", true),
+    ("[!]]", "]", false),
+    ("[!]]", "a", true),
+    ("[a-]", "-", true),
+    ("[a-]", "b", false),
+    (r"[\]]", "]", true),
+    ("[*?]", "*", true),
+    ("[*?]", "a", false),
+    ("[あ-お]", "う", true),
+    ("[/]", "/", false),
+    // {a,b}
+    ("{a,b}", "a", true),
+    ("{a,b}", "b", true),
+    ("{a,b}", "c", false),
+    ("{a,b}", "ab", false),
+    ("*.{rs,toml}", "Cargo.toml", true),
+    ("*.{rs,toml}", "lib.rs", true),
+    ("*.{rs,toml}", "lib.txt", false),
+    ("{src,tests}/**/*.rs", "tests/a/lib.rs", true),
+    ("{src,tests}/**/*.rs", "benches/a/lib.rs", false),
+    ("a{,b}c", "ac", true),
+    ("a{,b}c", "abc", true),
+    ("a{b,{c,d}e}f", "adef", true),
+    ("a{b,{c,d}e}f", "abf", true),
+    ("a{b,{c,d}e}f", "adf", false),
+    ("{**/a,b}", "x/y/a", true),
+    ("{src/**,*.rs}", "src/a/b", true),
+    ("{src/**,*.rs}", "lib.rs", true),
+    ("a/{b,**}/c", "a/x/y/c", true),
+    ("{a,{**}}", "x/y", true),
+    ("a,b}", "a,b}", true),
+    // Escape
+    (r"\*", "*", true),
+    (r"\*", "a", false),
+    (r"\?", "?", true),
+    (r"\[a]", "[a]", true),
+    (r"\{a,b}", "{a,b}", true),
+    (r"\!a", "!a", true),
+    (r"\\", r"\", true),
+];
+
+#[test]
+fn is_match() {
+    for (pattern, path, expected) in MATCH_TESTS {
+        let glob = SlashGlob::new(pattern).unwrap();
+        assert_eq!(glob.is_match(*path), *expected, "{:?}", (pattern, path));
+    }
+}
+
+#[test]
+fn negation() {
+    for (pattern, path, expected) in MATCH_TESTS {
+        let negated = format!("!{}", pattern);
+        let glob = SlashGlob::new(&negated).unwrap();
+        assert!(glob.is_negated());
+        assert_eq!(glob.is_match(*path), !*expected, "{:?}", (negated, path));
+    }
+    assert!(!SlashGlob::new("a!").unwrap().is_negated());
+    assert!(SlashGlob::new("a!").unwrap().is_match("a!"));
+}
+
+#[test]
+fn invalid_pattern() {
+    for (pattern, kind, pos) in &[
+        ("[", GlobErrorKind::UnclosedClass, 0),
+        ("a/[bc", GlobErrorKind::UnclosedClass, 2),
+        ("[]", GlobErrorKind::UnclosedClass, 0),
+        ("[!", GlobErrorKind::UnclosedClass, 0),
+        (r"[a\", GlobErrorKind::UnclosedClass, 0),
+        ("[z-a]", GlobErrorKind::InvalidRange, 1),
+        ("{a,b", GlobErrorKind::UnclosedBrace, 0),
+        ("x/{a,{b}", GlobErrorKind::UnclosedBrace, 2),
+        ("a**", GlobErrorKind::InvalidRecursive, 1),
+        ("**a", GlobErrorKind::InvalidRecursive, 0),
+        ("a/**b/c", GlobErrorKind::InvalidRecursive, 2),
+        ("a/b**/c", GlobErrorKind::InvalidRecursive, 3),
+        ("a{**}", GlobErrorKind::InvalidRecursive, 2),
+        ("a/{b,x**}", GlobErrorKind::InvalidRecursive, 6),
+        ("a/x{b,**/c}", GlobErrorKind::InvalidRecursive, 6),
+        ("{**}x", GlobErrorKind::InvalidRecursive, 0),
+        ("a/{b,{**}}.rs", GlobErrorKind::InvalidRecursive, 2),
+        ("a\\", GlobErrorKind::DanglingEscape, 1),
+        ("!あ\\", GlobErrorKind::DanglingEscape, 4),
+    ] {
+        let err = SlashGlob::new(pattern).unwrap_err();
+        assert_eq!(err.kind(), *kind, "{:?}", pattern);
+        assert_eq!(err.pos(), *pos, "{:?}", pattern);
+        assert_eq!(err.pattern(), *pattern);
+        let msg = err.to_string();
+        assert!(msg.contains(&kind.to_string()), "{:?}", msg);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn is_match_path() {
+    #[cfg(target_os = "windows")]
+    let tests = &[
+        (r"src\lib.rs", true),
+        (r"src\foo\bar\lib.rs", true),
+        ("src/foo/lib.rs", true),
+        (r"tests\lib.rs", false),
+    ];
+    #[cfg(not(target_os = "windows"))]
+    let tests = &[
+        ("src/lib.rs", true),
+        ("src/foo/bar/lib.rs", true),
+        ("tests/lib.rs", false),
+    ];
+
+    let glob = SlashGlob::new("src/**/*.rs").unwrap();
+    for (path, expected) in tests {
+        assert_eq!(glob.is_match_path(Path::new(path)), *expected, "{:?}", path);
+    }
+}
+
+#[test]
+fn display() {
+    let glob = SlashGlob::new("!src/**/*.{rs,toml}").unwrap();
+    assert_eq!(glob.as_str(), "!src/**/*.{rs,toml}");
+    assert_eq!(glob.to_string(), "!src/**/*.{rs,toml}");
+}
+
+#[test]
+fn long_path() {
+    // Matching must not backtrack exponentially
+    let glob = SlashGlob::new("a*a*a*a*a*a*a*a*a*a*b").unwrap();
+    let path = "a".repeat(1000);
+    assert!(!glob.is_match(&path));
+
+    let glob = SlashGlob::new("**/**/**/**/**/**/x").unwrap();
+    let path = "a/".repeat(1000);
+    assert!(!glob.is_match(&path));
+}
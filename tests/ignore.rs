@@ -0,0 +1,193 @@
+#![cfg(feature = "std")]
+
+use path_slash::ignore::{IgnoreError, IgnoreMatcher, Match};
+use path_slash::SlashPath;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn matcher(rules: &str) -> IgnoreMatcher {
+    let mut m = IgnoreMatcher::new("");
+    m.add_rules(rules).unwrap();
+    m
+}
+
+#[test]
+fn matched_slash() {
+    for (rules, path, is_dir, expected) in &[
+        ("", "foo", false, Match::None),
+        ("foo", "foo", false, Match::Ignore),
+        ("foo", "foo", true, Match::Ignore),
+        ("foo", "a/b/foo", false, Match::Ignore),
+        ("foo", "foo/bar", false, Match::None),
+        ("foo", "foobar", false, Match::None),
+        ("*.o", "a.o", false, Match::Ignore),
+        ("*.o", "x/y/a.o", false, Match::Ignore),
+        ("*.o", "a.c", false, Match::None),
+        // Comments and blank lines
+        ("# foo", "# foo", false, Match::None),
+        (r"\#foo", "#foo", false, Match::Ignore),
+        ("\n\n  \n", "", false, Match::None),
+        // Trailing spaces
+        ("foo   ", "foo", false, Match::Ignore),
+        (r"foo\ ", "foo ", false, Match::Ignore),
+        (r"foo\  ", "foo ", false, Match::Ignore),
+        ("foo\r", "foo", false, Match::Ignore),
+        // Directory only
+        ("build/", "build", true, Match::Ignore),
+        ("build/", "build", false, Match::None),
+        ("build/", "a/build", true, Match::Ignore),
+        // Anchored
+        ("/target", "target", true, Match::Ignore),
+        ("/target", "a/target", true, Match::None),
+        ("doc/frotz", "doc/frotz", false, Match::Ignore),
+        ("doc/frotz", "a/doc/frotz", false, Match::None),
+        ("doc/frotz/", "doc/frotz", true, Match::Ignore),
+        ("a/*.txt", "a/b.txt", false, Match::Ignore),
+        ("a/*.txt", "a/b/c.txt", false, Match::None),
+        // **
+        ("**/foo", "foo", false, Match::Ignore),
+        ("**/foo", "a/b/foo", false, Match::Ignore),
+        ("**/foo/bar", "a/foo/bar", false, Match::Ignore),
+        ("abc/**", "abc/x/y", false, Match::Ignore),
+        ("abc/**", "abc", true, Match::None),
+        ("a/**/b", "a/b", false, Match::Ignore),
+        ("a/**/b", "a/x/y/b", false, Match::Ignore),
+        // ** which is not a whole component is the same as *
+        ("foo**", "foobar", false, Match::Ignore),
+        ("foo**", "a/foo", false, Match::Ignore),
+        ("foo**", "foo/bar", false, Match::None),
+        ("**.log", "a/b.log", false, Match::Ignore),
+        ("**.log", "a/b/c", false, Match::None),
+        ("a**b", "x/ab", false, Match::Ignore),
+        ("a**b", "axyzb", false, Match::Ignore),
+        ("a**b", "a/b", false, Match::None),
+        ("/x/a**/b", "x/abc/b", false, Match::Ignore),
+        ("/x/a**/b", "x/a/c/b", false, Match::None),
+        // Negation
+        ("*.log\n!keep.log", "keep.log", false, Match::Whitelist),
+        ("*.log\n!keep.log", "a.log", false, Match::Ignore),
+        ("!keep.log\n*.log", "keep.log", false, Match::Ignore),
+        (r"\!foo", "!foo", false, Match::Ignore),
+        ("!", "!", false, Match::None),
+        // Braces are not special
+        ("{a,b}", "{a,b}", false, Match::Ignore),
+        ("{a,b}", "a", false, Match::None),
+    ][..]
+    {
+        let m = matcher(rules);
+        assert_eq!(
+            m.matched_slash(SlashPath::new(path).unwrap(), *is_dir),
+            *expected,
+            "{:?}",
+            (rules, path, is_dir),
+        );
+    }
+}
+
+// `\` can be a part of a file name only on non-Windows OS
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn escaped_backslash() {
+    for (rules, path, expected) in &[
+        (r"foo\\ ", r"foo\", Match::Ignore),
+        (r"foo\\  ", r"foo\", Match::Ignore),
+        (r"foo\\ ", r"foo\ ", Match::None),
+        (r"foo\\\ ", r"foo\ ", Match::Ignore),
+    ] {
+        let m = matcher(rules);
+        assert_eq!(
+            m.matched(Path::new(path), false),
+            *expected,
+            "{:?}",
+            (rules, path),
+        );
+    }
+}
+
+#[test]
+fn is_ignored() {
+    let m = matcher("/target/\nbuild/\n!build/keep.txt\n*.log\n!important.log\nnode_modules");
+    for (path, is_dir, expected) in &[
+        ("target", true, true),
+        ("target", false, false),
+        ("target/debug/foo", false, true),
+        ("src/target", true, false),
+        ("build/keep.txt", false, true),
+        ("src/build/a/b", false, true),
+        ("a.log", false, true),
+        ("src/important.log", false, false),
+        ("a/node_modules/b/c.js", false, true),
+        ("src/lib.rs", false, false),
+        ("./target/foo", false, true),
+        ("src/../target/foo", false, true),
+        ("", true, false),
+        (".", true, false),
+        ("..", true, false),
+        ("../target", true, false),
+    ] {
+        assert_eq!(
+            m.is_ignored(path, *is_dir),
+            *expected,
+            "{:?}",
+            (path, is_dir)
+        );
+    }
+}
+
+#[test]
+fn relative_to_root() {
+    let root = Path::new("project").join("sub");
+    let mut m = IgnoreMatcher::new(&root);
+    m.add_rules("/target\n*.o").unwrap();
+    assert_eq!(m.root(), root);
+
+    assert!(m.is_ignored(root.join("target"), true));
+    assert!(m.is_ignored(root.join("a").join("b.o"), false));
+    assert!(!m.is_ignored(root.join("a").join("target"), true));
+    assert_eq!(m.matched(root.join("x.o"), false), Match::Ignore);
+
+    // Relative paths which do not start with the root are relative to the root
+    assert!(m.is_ignored("target", true));
+    assert!(m.is_ignored(Path::new("a").join("b.o"), false));
+
+    // Absolute paths outside the root never match
+    let abs = std::env::current_dir().unwrap().join("target");
+    assert!(!m.is_ignored(&abs, true));
+    assert_eq!(m.matched(&abs, true), Match::None);
+}
+
+#[test]
+fn invalid_rule() {
+    let mut m = IgnoreMatcher::new("");
+    let err = m.add_rules("foo\n\n[a-\nbar").unwrap_err();
+    match &err {
+        IgnoreError::Glob { line, .. } => assert_eq!(*line, 3),
+        err => panic!("unexpected error: {:?}", err),
+    }
+    assert!(err.to_string().contains("line 3"), "{}", err);
+}
+
+#[test]
+fn from_file() {
+    let dir: PathBuf = std::env::temp_dir().join(format!(
+        "path-slash-test-ignore-from-file-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join(".gitignore");
+    fs::write(&file, "/target\n*.o\n!keep.o\n").unwrap();
+
+    let m = IgnoreMatcher::from_file(&file).unwrap();
+    assert_eq!(m.root(), dir);
+    assert!(m.is_ignored(dir.join("target"), true));
+    assert!(m.is_ignored(dir.join("src").join("a.o"), false));
+    assert!(!m.is_ignored(dir.join("src").join("keep.o"), false));
+    assert!(!m.is_ignored(dir.join("src").join("target"), true));
+
+    fs::remove_dir_all(&dir).unwrap();
+
+    match IgnoreMatcher::from_file(&file) {
+        Err(IgnoreError::Io(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
@@ -1,8 +1,15 @@
+#![cfg(feature = "std")]
+
 use lazy_static::lazy_static;
-use path_slash::{CowExt as _, PathBufExt as _, PathExt as _};
+use path_slash::{
+    from_slash_as, to_slash_from, CowExt as _, FromSlash as _, PathBufExt as _, PathExt as _,
+    PathStyle, SlashPath, SlashPathBuf,
+};
 use std::borrow::Cow;
-use std::ffi::OsStr;
-use std::path::{PathBuf, MAIN_SEPARATOR};
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
+use std::rc::Rc;
+use std::sync::Arc;
 
 lazy_static! {
     static ref FROM_SLASH_TESTS: Vec<(String, PathBuf)> = {
@@ -111,6 +118,67 @@ fn cow_from_backslash_lossy() {
     }
 }
 
+#[test]
+fn from_slash_other_owned_paths() {
+    for (input, expected) in FROM_SLASH_TESTS.iter() {
+        let backslash = input.replace('/', r"\");
+        let lossy: &OsStr = input.as_ref();
+        let backslash_lossy: &OsStr = backslash.as_ref();
+
+        // Compare as paths since `//` and `/` are the same path but different strings
+        assert_eq!(Path::new(&OsString::from_slash(input)), expected);
+        assert_eq!(Path::new(&OsString::from_slash_lossy(lossy)), expected);
+        assert_eq!(Path::new(&OsString::from_backslash(&backslash)), expected);
+        assert_eq!(
+            Path::new(&OsString::from_backslash_lossy(backslash_lossy)),
+            expected
+        );
+
+        assert_eq!(&*Box::<Path>::from_slash(input), expected);
+        assert_eq!(&*Box::<Path>::from_slash_lossy(lossy), expected);
+        assert_eq!(&*Box::<Path>::from_backslash(&backslash), expected);
+        assert_eq!(
+            &*Box::<Path>::from_backslash_lossy(backslash_lossy),
+            expected
+        );
+
+        assert_eq!(&*Rc::<Path>::from_slash(input), expected);
+        assert_eq!(&*Rc::<Path>::from_slash_lossy(lossy), expected);
+        assert_eq!(&*Rc::<Path>::from_backslash(&backslash), expected);
+        assert_eq!(
+            &*Rc::<Path>::from_backslash_lossy(backslash_lossy),
+            expected
+        );
+
+        assert_eq!(&*Arc::<Path>::from_slash(input), expected);
+        assert_eq!(&*Arc::<Path>::from_slash_lossy(lossy), expected);
+        assert_eq!(&*Arc::<Path>::from_backslash(&backslash), expected);
+        assert_eq!(
+            &*Arc::<Path>::from_backslash_lossy(backslash_lossy),
+            expected
+        );
+    }
+}
+
+#[test]
+fn slash_components() {
+    for (input, expected) in FROM_SLASH_TESTS.iter() {
+        let actual = SlashPath::new(input)
+            .unwrap()
+            .components()
+            .map(|c| c.as_str())
+            .collect::<Vec<_>>();
+        let expected = expected
+            .components()
+            .map(|c| match c {
+                Component::RootDir => "/",
+                c => c.as_os_str().to_str().unwrap(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected, "{:?}", input);
+    }
+}
+
 lazy_static! {
     static ref TO_SLASH_TESTS: Vec<(PathBuf, String)> = {
         [
@@ -197,6 +265,104 @@ fn to_slash_lossy_cow() {
     }
 }
 
+#[test]
+fn to_slash_other_paths() {
+    for (input, expected) in TO_SLASH_TESTS.iter() {
+        let expected = Some(Cow::Borrowed(expected.as_str()));
+        assert_eq!(input.as_os_str().to_slash(), expected);
+        assert_eq!(input.clone().into_os_string().to_slash(), expected);
+        assert_eq!(input.clone().into_boxed_path().to_slash(), expected);
+        assert_eq!(Rc::<Path>::from(input.as_path()).to_slash(), expected);
+        assert_eq!(Arc::<Path>::from(input.as_path()).to_slash(), expected);
+    }
+}
+
+#[test]
+fn to_slash_lossy_other_paths() {
+    for (input, expected) in TO_SLASH_TESTS.iter() {
+        assert_eq!(&input.as_os_str().to_slash_lossy(), expected);
+        assert_eq!(&input.clone().into_os_string().to_slash_lossy(), expected);
+        assert_eq!(&input.clone().into_boxed_path().to_slash_lossy(), expected);
+        assert_eq!(
+            &Rc::<Path>::from(input.as_path()).to_slash_lossy(),
+            expected
+        );
+        assert_eq!(
+            &Arc::<Path>::from(input.as_path()).to_slash_lossy(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn display_slash() {
+    for (input, expected) in TO_SLASH_TESTS.iter() {
+        assert_eq!(&input.display_slash().to_string(), expected);
+        assert_eq!(&input.as_os_str().display_slash().to_string(), expected);
+        assert_eq!(
+            &format!("{:?}", input.display_slash()),
+            &format!("{:?}", expected)
+        );
+    }
+}
+
+#[test]
+fn display_slash_debug_escape() {
+    let p = PathBuf::from_slash("foo/\"bar's\"/\tbaz");
+    assert_eq!(
+        format!("{:?}", p.display_slash()),
+        format!("{:?}", p.to_slash_lossy()),
+    );
+}
+
+#[test]
+fn write_slash() {
+    let mut buf = String::new();
+    for (input, expected) in TO_SLASH_TESTS.iter() {
+        buf.clear();
+        assert_eq!(input.write_slash(&mut buf), Ok(()));
+        assert_eq!(&buf, expected);
+
+        buf.clear();
+        input.as_os_str().write_slash(&mut buf).unwrap();
+        assert_eq!(&buf, expected);
+    }
+
+    let mut buf = String::from("prefix:");
+    PathBuf::from_slash("foo/bar")
+        .write_slash(&mut buf)
+        .unwrap();
+    assert_eq!(buf, "prefix:foo/bar");
+}
+
+#[test]
+fn write_slash_lossy() {
+    let mut buf = String::new();
+    for (input, expected) in TO_SLASH_TESTS.iter() {
+        buf.clear();
+        input.write_slash_lossy(&mut buf);
+        assert_eq!(&buf, expected);
+
+        buf.clear();
+        input.as_os_str().write_slash_lossy(&mut buf);
+        assert_eq!(&buf, expected);
+    }
+
+    let mut buf = String::from("prefix:");
+    PathBuf::from_slash("foo/bar").write_slash_lossy(&mut buf);
+    assert_eq!(buf, "prefix:foo/bar");
+}
+
+#[test]
+fn from_slash_into() {
+    let mut buf = PathBuf::from_slash("previous/long/content/to/be/cleared");
+    for (input, expected) in FROM_SLASH_TESTS.iter() {
+        PathBuf::from_slash_into(&mut buf, input);
+        assert_eq!(&buf, expected);
+        assert_eq!(buf.as_os_str(), PathBuf::from_slash(input).as_os_str());
+    }
+}
+
 #[test]
 fn from_slash_to_slash() {
     for (_, path) in TO_SLASH_TESTS.iter() {
@@ -206,3 +372,121 @@ fn from_slash_to_slash() {
         );
     }
 }
+
+#[test]
+fn to_slash_clean() {
+    for (input, expected) in &[
+        ("", "."),
+        ("foo", "foo"),
+        ("/foo/", "/foo"),
+        ("./foo", "foo"),
+        ("foo/./bar/../piyo", "foo/piyo"),
+        ("../foo/..", ".."),
+        ("/../foo", "/foo"),
+        ("あ/い/../う/", "あ/う"),
+    ] {
+        let path = PathBuf::from_slash(input);
+        assert_eq!(path.to_slash_clean().unwrap(), *expected, "{:?}", path);
+    }
+}
+
+#[test]
+fn diff_paths() {
+    #[cfg(target_os = "windows")]
+    let tests = &[
+        (
+            r"C:\foo\src\lib.rs",
+            r"C:\foo\target\debug",
+            Some("../../src/lib.rs"),
+        ),
+        (r"C:\foo\.\bar", r"C:\foo\bar\..", Some("bar")),
+        (r"foo\bar", "foo", Some("bar")),
+        (r"C:\foo", r"D:\foo", None),
+        (r"C:\foo", "foo", None),
+    ];
+    #[cfg(not(target_os = "windows"))]
+    let tests = &[
+        (
+            "/foo/src/lib.rs",
+            "/foo/target/debug",
+            Some("../../src/lib.rs"),
+        ),
+        ("/foo/./bar", "/foo/bar/..", Some("bar")),
+        ("foo/bar", "foo", Some("bar")),
+        ("/foo", "foo", None),
+    ];
+
+    for (path, base, expected) in tests {
+        assert_eq!(
+            path_slash::diff_paths(path, base),
+            expected.map(SlashPathBuf::from),
+            "{:?}",
+            (path, base),
+        );
+    }
+}
+
+#[test]
+fn diff_paths_canonicalized() {
+    let rel = path_slash::diff_paths_canonicalized("src/lib.rs", "tests").unwrap();
+    assert_eq!(rel.unwrap(), "../src/lib.rs");
+    assert!(path_slash::diff_paths_canonicalized("src/lib.rs", "does-not-exist").is_err());
+}
+
+#[test]
+fn from_slash_as_and_to_slash_from() {
+    for (slash, posix, windows) in &[
+        ("", "", ""),
+        ("foo/bar/piyo.txt", "foo/bar/piyo.txt", r"foo\bar\piyo.txt"),
+        ("/foo/bar/", "/foo/bar/", r"\foo\bar\"),
+        ("C:/foo/bar", "C:/foo/bar", r"C:\foo\bar"),
+        (
+            r"\\server\share/foo",
+            r"\\server\share/foo",
+            r"\\server\share\foo",
+        ),
+        ("/あ/い/う", "/あ/い/う", r"\あ\い\う"),
+    ] {
+        assert_eq!(
+            from_slash_as(slash, PathStyle::Posix),
+            *posix,
+            "{:?}",
+            slash
+        );
+        assert_eq!(
+            from_slash_as(slash, PathStyle::Windows),
+            *windows,
+            "{:?}",
+            slash
+        );
+        assert_eq!(
+            to_slash_from(posix, PathStyle::Posix),
+            *slash,
+            "{:?}",
+            posix
+        );
+        assert_eq!(
+            to_slash_from(windows, PathStyle::Windows),
+            *slash,
+            "{:?}",
+            windows
+        );
+    }
+    // '\' is not a separator of POSIX paths
+    assert_eq!(to_slash_from(r"foo\bar", PathStyle::Posix), r"foo\bar");
+}
+
+#[test]
+fn path_style_native() {
+    let style = PathStyle::native();
+    assert_eq!(style.separator(), MAIN_SEPARATOR);
+    for (input, _) in FROM_SLASH_TESTS.iter() {
+        let native = from_slash_as(input, style);
+        assert_eq!(
+            PathBuf::from(&native),
+            PathBuf::from_slash(input),
+            "{:?}",
+            input
+        );
+    }
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "macros")]
+
+use path_slash::PathBufExt as _;
+use path_slash::{concat_slash_path, slash_path};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn slash_path_same_as_from_slash() {
+    macro_rules! check {
+        ($($lit:literal),*) => {{
+            $(
+                let p: &'static Path = slash_path!($lit);
+                assert_eq!(p.as_os_str(), PathBuf::from_slash($lit).as_os_str(), "{:?}", $lit);
+            )*
+        }};
+    }
+
+    check!(
+        "",
+        "/",
+        "foo",
+        "foo/bar/piyo.txt",
+        "/foo/bar/",
+        "foo//bar",
+        "./foo/../bar",
+        "C:/Users/foo",
+        "//server/share/a",
+        "あ/い/う",
+        "foo\\bar/baz",
+        r"foo/b\ar",
+        r#"foo/"bar"/baz"#,
+        "foo/\x41\u{3042}\t/bar",
+        "foo/\
+         bar"
+    );
+}
+
+#[test]
+fn slash_path_trailing_comma() {
+    assert_eq!(slash_path!("foo/bar",), PathBuf::from_slash("foo/bar"));
+}
+
+#[test]
+fn slash_path_in_macro_rules_expr() {
+    macro_rules! wrap {
+        ($e:expr) => {
+            slash_path!($e)
+        };
+    }
+    assert_eq!(wrap!("foo/bar"), PathBuf::from_slash("foo/bar"));
+}
+
+#[test]
+fn concat_slash_path_same_as_from_slash() {
+    let tests: Vec<(&'static Path, &str)> = vec![
+        (concat_slash_path!("foo"), "foo"),
+        (concat_slash_path!("foo/", "bar"), "foo/bar"),
+        (concat_slash_path!("foo", "bar"), "foobar"),
+        (
+            concat_slash_path!("/a", "/b/", r"c\d", "/e.txt",),
+            "/a/b/c\\d/e.txt",
+        ),
+        (concat_slash_path!("", "x/y", ""), "x/y"),
+    ];
+
+    for (actual, slash) in tests {
+        assert_eq!(
+            actual.as_os_str(),
+            PathBuf::from_slash(slash).as_os_str(),
+            "{:?}",
+            slash
+        );
+    }
+}
+
+#[test]
+fn native_separator() {
+    #[cfg(target_os = "windows")]
+    assert_eq!(slash_path!("a/b/c").as_os_str(), r"a\b\c");
+    #[cfg(not(target_os = "windows"))]
+    assert_eq!(slash_path!("a/b/c").as_os_str(), "a/b/c");
+}
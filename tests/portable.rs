@@ -0,0 +1,109 @@
+use path_slash::{validate_portable, PortabilityIssueKind as Kind};
+
+#[test]
+fn portable() {
+    for path in &[
+        "",
+        "foo",
+        "foo/bar/piyo.txt",
+        "/foo/bar/",
+        "./foo/../bar",
+        ".hidden/.config",
+        "foo bar/a-b_c~d (1).txt",
+        "console/nul_/COM10/LPT/auxiliary",
+        "CONIN/CONOUT/COM⁴/LPT⁴/COM₁",
+        "あ/い/う.txt",
+    ] {
+        assert_eq!(validate_portable(path), Ok(()), "{:?}", path);
+    }
+}
+
+#[test]
+fn not_portable() {
+    for (path, expected) in &[
+        ("CON", &[(0, Kind::ReservedName)][..]),
+        ("foo/nul.txt", &[(4, Kind::ReservedName)][..]),
+        ("foo/Com1.tar.gz/bar", &[(4, Kind::ReservedName)][..]),
+        ("lpt9 .txt", &[(0, Kind::ReservedName)][..]),
+        ("COM0", &[(0, Kind::ReservedName)][..]),
+        ("lpt0.log", &[(0, Kind::ReservedName)][..]),
+        ("foo/com¹", &[(4, Kind::ReservedName)][..]),
+        ("COM².txt", &[(0, Kind::ReservedName)][..]),
+        ("COM³", &[(0, Kind::ReservedName)][..]),
+        ("LPT¹", &[(0, Kind::ReservedName)][..]),
+        ("lpt²", &[(0, Kind::ReservedName)][..]),
+        ("LPT³.txt", &[(0, Kind::ReservedName)][..]),
+        ("foo/conin$", &[(4, Kind::ReservedName)][..]),
+        ("CONOUT$.txt", &[(0, Kind::ReservedName)][..]),
+        ("a<b", &[(1, Kind::ReservedChar('<'))][..]),
+        (
+            "C:/foo|bar",
+            &[(1, Kind::ReservedChar(':')), (6, Kind::ReservedChar('|'))][..],
+        ),
+        (
+            r"foo\bar*",
+            &[(3, Kind::ReservedChar('\\')), (7, Kind::ReservedChar('*'))][..],
+        ),
+        ("あ\"い", &[(3, Kind::ReservedChar('"'))][..]),
+        ("a\0b", &[(1, Kind::ControlChar('\0'))][..]),
+        (
+            "foo/a\nb\x7f",
+            &[(5, Kind::ControlChar('\n')), (7, Kind::ControlChar('\x7f'))][..],
+        ),
+        ("foo./bar", &[(3, Kind::TrailingDotOrSpace)][..]),
+        ("foo/bar ", &[(7, Kind::TrailingDotOrSpace)][..]),
+        ("...", &[(2, Kind::TrailingDotOrSpace)][..]),
+        (
+            "AUX.",
+            &[(0, Kind::ReservedName), (3, Kind::TrailingDotOrSpace)][..],
+        ),
+    ] {
+        let err = validate_portable(path).unwrap_err();
+        let actual = err
+            .issues()
+            .iter()
+            .map(|i| (i.pos(), i.kind()))
+            .collect::<Vec<_>>();
+        assert_eq!(&actual[..], *expected, "{:?}", path);
+        assert_eq!(err.path(), *path);
+    }
+}
+
+#[test]
+fn too_long() {
+    let name = "a".repeat(256);
+    let err = validate_portable(&name).unwrap_err();
+    let kinds = err.issues().iter().map(|i| i.kind()).collect::<Vec<_>>();
+    assert_eq!(kinds, [Kind::ComponentTooLong]);
+    assert_eq!(err.issues()[0].pos(), 0);
+
+    let err = validate_portable(&format!("foo/{}", name)).unwrap_err();
+    let issues = err
+        .issues()
+        .iter()
+        .map(|i| (i.pos(), i.kind()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        [(4, Kind::ComponentTooLong), (4, Kind::PathTooLong)]
+    );
+
+    assert!(validate_portable(&"a".repeat(255)).is_ok());
+
+    let long = vec!["a".repeat(100); 3].join("/");
+    assert!(validate_portable(&long[..259]).is_ok());
+    let err = validate_portable(&long).unwrap_err();
+    assert_eq!(err.issues().len(), 1);
+    assert_eq!(err.issues()[0].kind(), Kind::PathTooLong);
+    assert_eq!(err.issues()[0].pos(), 202);
+}
+
+#[test]
+fn display() {
+    let err = validate_portable("a/CON/b?").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "path \"a/CON/b?\" is not portable: reserved file name on Windows at byte offset 2, \
+         reserved character '?' at byte offset 7",
+    );
+}
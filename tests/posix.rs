@@ -0,0 +1,93 @@
+use path_slash::posix::{self, Component};
+
+const COMPONENTS_TESTS: &[(&str, &[&str])] = &[
+    ("", &[]),
+    ("/", &["/"]),
+    ("//", &["/"]),
+    ("foo", &["foo"]),
+    ("/foo", &["/", "foo"]),
+    ("foo/", &["foo"]),
+    ("/foo/", &["/", "foo"]),
+    (".", &["."]),
+    ("./foo", &[".", "foo"]),
+    ("../foo", &["..", "foo"]),
+    ("foo/.", &["foo"]),
+    ("foo/..", &["foo", ".."]),
+    ("foo//bar", &["foo", "bar"]),
+    ("foo/./bar", &["foo", "bar"]),
+    ("foo/../bar", &["foo", "..", "bar"]),
+    ("/./foo", &["/", "foo"]),
+    (".foo/bar", &[".foo", "bar"]),
+    (r"foo\bar", &[r"foo\bar"]),
+    ("C:/foo", &["C:", "foo"]),
+    ("/あ/い/う/え/お/", &["/", "あ", "い", "う", "え", "お"]),
+];
+
+#[test]
+fn components() {
+    for (input, expected) in COMPONENTS_TESTS {
+        let actual = posix::components(input)
+            .map(Component::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(&actual, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn components_rev() {
+    for (input, expected) in COMPONENTS_TESTS {
+        let mut actual = posix::components(input)
+            .rev()
+            .map(Component::as_str)
+            .collect::<Vec<_>>();
+        actual.reverse();
+        assert_eq!(&actual, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn components_same_as_std() {
+    if cfg!(target_os = "windows") {
+        return;
+    }
+    for (input, _) in COMPONENTS_TESTS {
+        let actual = posix::components(input)
+            .map(Component::as_str)
+            .collect::<Vec<_>>();
+        let expected = std::path::Path::new(input)
+            .components()
+            .map(|c| c.as_os_str().to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn join() {
+    for (base, path, expected) in &[
+        ("", "", ""),
+        ("", "foo", "foo"),
+        ("foo", "", "foo/"),
+        ("foo", "bar", "foo/bar"),
+        ("foo/", "bar", "foo/bar"),
+        ("/", "bar", "/bar"),
+        ("foo", "/bar", "/bar"),
+        ("foo", "../bar", "foo/../bar"),
+        ("foo", r"C:\bar", r"foo/C:\bar"),
+    ] {
+        assert_eq!(&posix::join(base, path), expected, "{:?}", (base, path));
+    }
+}
+
+#[test]
+fn from_backslash() {
+    for (input, expected) in &[
+        ("", ""),
+        (r"\", "/"),
+        (r"foo\bar", "foo/bar"),
+        (r"\foo\bar\", "/foo/bar/"),
+        ("foo/bar", "foo/bar"),
+    ] {
+        assert_eq!(posix::from_backslash(input), *expected, "{:?}", input);
+    }
+}
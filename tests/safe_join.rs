@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+use path_slash::{safe_join, SafeJoinError};
+use std::path::{Path, PathBuf};
+
+fn join_all(base: &Path, comps: &[&str]) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for comp in comps {
+        path.push(comp);
+    }
+    path
+}
+
+#[test]
+fn safe_join_ok() {
+    let base = Path::new("root").join("dir");
+    for (slash, expected) in &[
+        ("", &[][..]),
+        (".", &[][..]),
+        ("foo", &["foo"][..]),
+        ("foo/bar.txt", &["foo", "bar.txt"][..]),
+        ("foo//bar/", &["foo", "bar"][..]),
+        ("./foo/./bar", &["foo", "bar"][..]),
+        ("foo/../bar", &["bar"][..]),
+        ("foo/bar/../../baz", &["baz"][..]),
+        ("foo/..", &[][..]),
+        (r"foo\bar", &["foo", "bar"][..]),
+        ("...", &["..."][..]),
+        ("..foo/foo..", &["..foo", "foo.."][..]),
+        ("ab:c", &["ab:c"][..]),
+        ("あ/い", &["あ", "い"][..]),
+    ] {
+        assert_eq!(
+            safe_join(&base, slash),
+            Ok(join_all(&base, expected)),
+            "{:?}",
+            slash
+        );
+    }
+}
+
+#[test]
+fn safe_join_rejected() {
+    for (slash, expected) in &[
+        ("..", SafeJoinError::Traversal),
+        ("../foo", SafeJoinError::Traversal),
+        ("foo/../../bar", SafeJoinError::Traversal),
+        ("foo/../..", SafeJoinError::Traversal),
+        (r"..\foo", SafeJoinError::Traversal),
+        (r"foo\..\..\bar", SafeJoinError::Traversal),
+        ("/", SafeJoinError::Absolute),
+        ("/etc/passwd", SafeJoinError::Absolute),
+        ("//foo", SafeJoinError::Absolute),
+        (r"\foo", SafeJoinError::Absolute),
+        ("C:", SafeJoinError::Prefix),
+        ("a:b", SafeJoinError::Prefix),
+        ("C:foo", SafeJoinError::Prefix),
+        ("C:/Windows", SafeJoinError::Prefix),
+        ("foo/C:bar", SafeJoinError::Prefix),
+        (r"foo\d:", SafeJoinError::Prefix),
+        (r"\\server\share\foo", SafeJoinError::Prefix),
+        ("//server/share/foo", SafeJoinError::Prefix),
+        (r"\\?\C:\foo", SafeJoinError::Prefix),
+        ("foo\0bar", SafeJoinError::Nul),
+        ("../\0", SafeJoinError::Nul),
+    ] {
+        let err = safe_join("root", slash).unwrap_err();
+        assert_eq!(err, *expected, "{:?}", slash);
+        assert!(!err.to_string().is_empty());
+    }
+}
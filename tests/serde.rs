@@ -0,0 +1,271 @@
+#![cfg(feature = "serde")]
+
+use path_slash::serde::{option, slash, slash_lossy, vec};
+use serde::de::value::{Error, SeqDeserializer};
+use serde::de::{Error as _, IntoDeserializer, Visitor};
+use serde::ser::{self, Impossible, Serialize};
+use serde::{forward_to_deserialize_any, Deserializer, Serializer};
+use serde_crate as serde;
+use std::path::PathBuf;
+
+// Minimal data format to test the serialization without depending on a specific format crate
+#[derive(Debug, PartialEq)]
+enum Value {
+    Str(String),
+    Null,
+    Seq(Vec<Value>),
+}
+
+fn str(s: &str) -> Value {
+    Value::Str(s.to_string())
+}
+
+struct ValueSerializer;
+
+macro_rules! unsupported {
+    ($($method:ident($($arg:ty),*) -> $ret:ty;)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ret, Error> {
+                Err(Error::custom(concat!(stringify!($method), " is not supported")))
+            }
+        )*
+    };
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = Impossible<Value, Error>;
+    type SerializeTupleStruct = Impossible<Value, Error>;
+    type SerializeTupleVariant = Impossible<Value, Error>;
+    type SerializeMap = Impossible<Value, Error>;
+    type SerializeStruct = Impossible<Value, Error>;
+    type SerializeStructVariant = Impossible<Value, Error>;
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(str(v))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, v: &T) -> Result<Value, Error> {
+        v.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Value, Error> {
+        Err(Error::custom("serialize_newtype_struct is not supported"))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Value, Error> {
+        Err(Error::custom("serialize_newtype_variant is not supported"))
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> Value;
+        serialize_i8(i8) -> Value;
+        serialize_i16(i16) -> Value;
+        serialize_i32(i32) -> Value;
+        serialize_i64(i64) -> Value;
+        serialize_u8(u8) -> Value;
+        serialize_u16(u16) -> Value;
+        serialize_u32(u32) -> Value;
+        serialize_u64(u64) -> Value;
+        serialize_f32(f32) -> Value;
+        serialize_f64(f64) -> Value;
+        serialize_char(char) -> Value;
+        serialize_bytes(&[u8]) -> Value;
+        serialize_unit() -> Value;
+        serialize_unit_struct(&'static str) -> Value;
+        serialize_unit_variant(&'static str, u32, &'static str) -> Value;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant;
+    }
+}
+
+struct SeqSerializer(Vec<Value>);
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, v: &T) -> Result<(), Error> {
+        self.0.push(v.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Seq(self.0))
+    }
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Str(s) => visitor.visit_string(s),
+            Value::Null => visitor.visit_none(),
+            Value::Seq(v) => visitor.visit_seq(SeqDeserializer::new(v.into_iter())),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            v => visitor.visit_some(v),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn native(s: &str) -> PathBuf {
+    PathBuf::from(s.replace('/', "\\"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn native(s: &str) -> PathBuf {
+    PathBuf::from(s)
+}
+
+#[test]
+fn slash_roundtrip() {
+    for s in &[
+        "",
+        "foo",
+        "foo/bar/piyo.txt",
+        "/foo/bar",
+        "foo/bar/",
+        "あ/い/う",
+    ] {
+        let p = native(s);
+        assert_eq!(slash::serialize(&p, ValueSerializer).unwrap(), str(s));
+        assert_eq!(slash::deserialize(str(s)).unwrap(), p);
+        assert_eq!(slash_lossy::serialize(&p, ValueSerializer).unwrap(), str(s));
+        assert_eq!(slash_lossy::deserialize(str(s)).unwrap(), p);
+    }
+}
+
+#[test]
+fn slash_deserialize_error() {
+    let err = slash::deserialize(Value::Seq(vec![])).unwrap_err();
+    assert!(err.to_string().contains("invalid type"), "{}", err);
+}
+
+#[test]
+fn option_roundtrip() {
+    let p = Some(native("foo/bar"));
+    assert_eq!(
+        option::serialize(&p, ValueSerializer).unwrap(),
+        str("foo/bar")
+    );
+    assert_eq!(option::deserialize(str("foo/bar")).unwrap(), p);
+
+    let p: Option<PathBuf> = None;
+    assert_eq!(option::serialize(&p, ValueSerializer).unwrap(), Value::Null);
+    assert_eq!(option::deserialize(Value::Null).unwrap(), p);
+}
+
+#[test]
+fn vec_roundtrip() {
+    let paths = vec![native("foo/bar"), native("/piyo"), native("")];
+    let value = Value::Seq(vec![str("foo/bar"), str("/piyo"), str("")]);
+    assert_eq!(vec::serialize(&paths, ValueSerializer).unwrap(), value);
+    assert_eq!(vec::deserialize(value).unwrap(), paths);
+
+    let empty: Vec<PathBuf> = vec![];
+    assert_eq!(
+        vec::serialize(&empty, ValueSerializer).unwrap(),
+        Value::Seq(vec![])
+    );
+    assert_eq!(vec::deserialize(Value::Seq(vec![])).unwrap(), empty);
+}
+
+#[cfg(not(target_os = "windows"))]
+fn non_unicode_path() -> PathBuf {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt as _;
+    PathBuf::from(OsStr::from_bytes(b"foo/\xff/bar"))
+}
+
+#[cfg(target_os = "windows")]
+fn non_unicode_path() -> PathBuf {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt as _;
+    let mut v = "foo\\".encode_utf16().collect::<Vec<_>>();
+    v.push(0xd800); // Unpaired surrogate
+    PathBuf::from(OsString::from_wide(&v))
+}
+
+#[test]
+fn non_unicode_path_error() {
+    let p = non_unicode_path();
+
+    let err = slash::serialize(&p, ValueSerializer).unwrap_err();
+    assert!(err.to_string().contains("non-Unicode"), "{}", err);
+    let err = option::serialize(&Some(&p), ValueSerializer).unwrap_err();
+    assert!(err.to_string().contains("non-Unicode"), "{}", err);
+    let err = vec::serialize(&[&p], ValueSerializer).unwrap_err();
+    assert!(err.to_string().contains("non-Unicode"), "{}", err);
+
+    let lossy = slash_lossy::serialize(&p, ValueSerializer).unwrap();
+    #[cfg(not(target_os = "windows"))]
+    assert_eq!(lossy, str("foo/\u{FFFD}/bar"));
+    #[cfg(target_os = "windows")]
+    assert_eq!(lossy, str("foo/\u{FFFD}"));
+}
+
+#[test]
+fn serde_with_attribute() {
+    // Check the signatures of the functions are suitable for `#[serde(with = "...")]`
+    struct Config {
+        dir: PathBuf,
+        cache: Option<PathBuf>,
+        sources: Vec<PathBuf>,
+    }
+
+    let c = Config {
+        dir: native("foo"),
+        cache: None,
+        sources: vec![native("foo/bar.rs")],
+    };
+    slash::serialize(&c.dir, ValueSerializer).unwrap();
+    option::serialize(&c.cache, ValueSerializer).unwrap();
+    vec::serialize(&c.sources, ValueSerializer).unwrap();
+}
@@ -0,0 +1,691 @@
+#[cfg(feature = "std")]
+use path_slash::PathBufExt as _;
+use path_slash::{SlashComponent, SlashPath, SlashPathBuf};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "std")]
+const SLASH_PATHS: &[&str] = &[
+    "",
+    "/",
+    "foo",
+    "/foo",
+    "foo/",
+    "/foo/",
+    "./foo",
+    "../foo",
+    "foo/..",
+    "foo/bar",
+    "foo/../bar",
+    "あ/い/う/え/お/",
+    "/あ/い/う/え/お",
+];
+
+#[cfg(feature = "std")]
+#[test]
+fn from_path_to_path_buf() {
+    for s in SLASH_PATHS {
+        let path = PathBuf::from_slash(s);
+        let slash = SlashPathBuf::from_path(&path).unwrap();
+        assert_eq!(slash, *s);
+        assert_eq!(SlashPathBuf::from_path_lossy(&path), *s);
+        assert_eq!(slash.as_slash_path().to_path_buf(), path);
+        assert_eq!(slash.as_slash_path().to_path(), path.as_path());
+        assert_eq!(PathBuf::from(slash), path);
+    }
+}
+
+#[test]
+fn borrowed_and_owned() {
+    let p = SlashPath::new("foo/bar").unwrap();
+    let b: SlashPathBuf = p.to_owned();
+    assert_eq!(p, &b);
+    assert_eq!(b.as_slash_path(), p);
+    assert_eq!(&*b, p);
+    assert_eq!(SlashPathBuf::from(p), b);
+
+    let c: Cow<'_, SlashPath> = Cow::Borrowed(p);
+    assert_eq!(SlashPathBuf::from(c), b);
+
+    let s: &str = p.into();
+    assert_eq!(s, "foo/bar");
+
+    let s: String = b.clone().into();
+    assert_eq!(s, "foo/bar");
+    assert_eq!(b.into_string(), "foo/bar");
+}
+
+#[test]
+fn compare_with_strings() {
+    let p = SlashPath::new("foo/bar").unwrap();
+    assert_eq!(p, "foo/bar");
+    assert_eq!("foo/bar", p);
+    assert_eq!(p, "foo/bar".to_string());
+
+    let b = SlashPathBuf::from("foo/bar");
+    assert_eq!(b, "foo/bar");
+    assert_eq!(b, *"foo/bar");
+    assert_eq!(b, "foo/bar".to_string());
+    assert_ne!(b, "foo/baz");
+}
+
+#[test]
+fn hash_and_ord() {
+    let mut m = HashMap::new();
+    m.insert(SlashPathBuf::from("foo/bar"), 1);
+    m.insert(SlashPathBuf::from("foo/baz"), 2);
+    assert_eq!(m.get(SlashPath::new("foo/bar").unwrap()), Some(&1));
+    assert_eq!(m.get("foo/baz"), Some(&2));
+    assert_eq!(m.get(SlashPath::new("foo").unwrap()), None);
+
+    let s = ["b", "a/b", "a"]
+        .iter()
+        .map(|s| SlashPathBuf::from(*s))
+        .collect::<BTreeSet<_>>();
+    let v = s.iter().map(|p| p.as_str()).collect::<Vec<_>>();
+    assert_eq!(v, ["a", "a/b", "b"]);
+}
+
+#[test]
+fn display_and_debug() {
+    let p = SlashPathBuf::from("foo/\"bar\"");
+    assert_eq!(format!("{}", p), "foo/\"bar\"");
+    assert_eq!(format!("{:?}", p), r#""foo/\"bar\"""#);
+    assert_eq!(format!("{:>8}", SlashPath::new("a/b").unwrap()), "     a/b");
+}
+
+#[test]
+fn as_ref() {
+    fn takes_slash_path<P: AsRef<SlashPath> + ?Sized>(p: &P) -> &str {
+        p.as_ref().as_str()
+    }
+    assert_eq!(takes_slash_path(&SlashPathBuf::from("foo/bar")), "foo/bar");
+    assert_eq!(
+        takes_slash_path(SlashPath::new("foo/bar").unwrap()),
+        "foo/bar"
+    );
+}
+
+#[test]
+fn backslash() {
+    for input in &[
+        r"foo\bar",
+        r"\foo",
+        r"C:\foo",
+        r"\\server\share\foo",
+        r"foo/bar\",
+    ] {
+        assert_eq!(SlashPath::new(input), None, "{:?}", input);
+    }
+    for input in &[
+        r"\\server\share",
+        r"\\server\share/foo",
+        r"\\?\C:/foo",
+        r"\\?\UNC\server\share/foo",
+        r"\\.\COM1",
+    ] {
+        assert_eq!(SlashPath::new(input).unwrap(), *input);
+    }
+
+    assert_eq!(SlashPathBuf::from(r"foo\bar\baz"), "foo/bar/baz");
+    assert_eq!(SlashPathBuf::from(r"C:\foo"), "C:/foo");
+    assert_eq!(
+        SlashPathBuf::from(r"\\server\share\foo".to_string()),
+        r"\\server\share/foo"
+    );
+
+    let mut p = SlashPathBuf::from("foo");
+    p.push(r"bar\baz");
+    assert_eq!(p, "foo/bar/baz");
+}
+
+#[cfg(all(feature = "std", not(target_os = "windows")))]
+#[test]
+fn non_utf8_path() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt as _;
+
+    let p = Path::new(OsStr::from_bytes(b"aaa/\xc3"));
+    assert_eq!(SlashPathBuf::from_path(p), None);
+    assert_eq!(SlashPathBuf::from_path_lossy(p), "aaa/\u{FFFD}");
+
+    // `\` is a part of a file name on non-Windows OS
+    let p = Path::new(r"aaa/b\c");
+    assert_eq!(SlashPathBuf::from_path(p), None);
+    assert_eq!(SlashPathBuf::from_path_lossy(p), "aaa/b\u{FFFD}c");
+}
+
+#[cfg(all(feature = "std", target_os = "windows"))]
+#[test]
+fn windows_path() {
+    let p = SlashPathBuf::from_path(Path::new(r"C:\foo\bar")).unwrap();
+    assert_eq!(p, "C:/foo/bar");
+    assert_eq!(
+        p.as_slash_path().to_path_buf(),
+        PathBuf::from(r"C:\foo\bar")
+    );
+}
+
+const COMPONENTS_TESTS: &[(&str, &[&str], &str)] = &[
+    ("", &[], ""),
+    ("/", &["/"], "/"),
+    ("//", &["/"], "/"),
+    ("foo", &["foo"], "foo"),
+    ("/foo", &["/", "foo"], "/foo"),
+    ("foo/", &["foo"], "foo"),
+    ("/foo/", &["/", "foo"], "/foo"),
+    (".", &["."], "."),
+    ("./foo", &[".", "foo"], "./foo"),
+    ("../foo", &["..", "foo"], "../foo"),
+    ("foo/.", &["foo"], "foo"),
+    ("foo/..", &["foo", ".."], "foo/.."),
+    ("foo/bar", &["foo", "bar"], "foo/bar"),
+    ("foo//bar", &["foo", "bar"], "foo/bar"),
+    ("foo/../bar", &["foo", "..", "bar"], "foo/../bar"),
+    ("foo/./bar", &["foo", "bar"], "foo/bar"),
+    ("/./foo", &["/", "foo"], "/foo"),
+    (
+        "/あ/い/う/え/お/",
+        &["/", "あ", "い", "う", "え", "お"],
+        "/あ/い/う/え/お",
+    ),
+    ("C:", &["C:"], "C:"),
+    ("C:foo", &["C:", "foo"], "C:foo"),
+    ("C:./foo", &["C:", "foo"], "C:foo"),
+    ("C:.", &["C:"], "C:"),
+    ("C:/foo/bar", &["C:", "/", "foo", "bar"], "C:/foo/bar"),
+    (r"\\?\C:/foo", &[r"\\?\C:", "/", "foo"], r"\\?\C:/foo"),
+    (r"\\?\C:", &[r"\\?\C:"], r"\\?\C:"),
+    (
+        r"\\server\share",
+        &[r"\\server\share", "/"],
+        r"\\server\share/",
+    ),
+    (
+        r"\\server\share/foo",
+        &[r"\\server\share", "/", "foo"],
+        r"\\server\share/foo",
+    ),
+    (
+        "//server/share/foo",
+        &["//server/share", "/", "foo"],
+        "//server/share/foo",
+    ),
+    (
+        r"\\?\UNC\server\share/foo",
+        &[r"\\?\UNC\server\share", "/", "foo"],
+        r"\\?\UNC\server\share/foo",
+    ),
+    (r"\\.\COM1/foo", &[r"\\.\COM1", "/", "foo"], r"\\.\COM1/foo"),
+    ("//foo", &["/", "foo"], "/foo"),
+];
+
+// Join the components into a slash path again
+fn join_components<'a>(comps: impl Iterator<Item = SlashComponent<'a>>) -> String {
+    let mut buf = String::new();
+    let mut need_sep = false;
+    for c in comps {
+        if need_sep {
+            buf.push('/');
+        }
+        buf.push_str(c.as_str());
+        need_sep = match c {
+            SlashComponent::Prefix(_) | SlashComponent::RootDir => false,
+            _ => true,
+        };
+    }
+    buf
+}
+
+#[test]
+fn components() {
+    for (input, expected, _) in COMPONENTS_TESTS {
+        let actual = SlashPath::new(input)
+            .unwrap()
+            .components()
+            .map(SlashComponent::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(&actual, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn components_rev() {
+    for (input, expected, _) in COMPONENTS_TESTS {
+        let mut actual = SlashPath::new(input)
+            .unwrap()
+            .components()
+            .rev()
+            .map(SlashComponent::as_str)
+            .collect::<Vec<_>>();
+        actual.reverse();
+        assert_eq!(&actual, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn components_both_ends() {
+    for (input, expected, _) in COMPONENTS_TESTS {
+        let mut comps = SlashPath::new(input).unwrap().components();
+        let mut front = vec![];
+        let mut back = vec![];
+        while let Some(c) = comps.next() {
+            front.push(c.as_str());
+            match comps.next_back() {
+                Some(c) => back.push(c.as_str()),
+                None => break,
+            }
+        }
+        back.reverse();
+        front.extend(back);
+        assert_eq!(&front, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn components_collapse() {
+    for (input, _, expected) in COMPONENTS_TESTS {
+        let comps = SlashPath::new(input).unwrap().components();
+        assert_eq!(join_components(comps.clone()), *expected, "{:?}", input);
+        assert_eq!(
+            join_components(comps.as_path().components()),
+            *expected,
+            "{:?}",
+            input,
+        );
+    }
+}
+
+#[test]
+fn components_as_path() {
+    let mut comps = SlashPath::new("/foo/./bar/piyo/").unwrap().components();
+    assert_eq!(comps.as_path(), "/foo/./bar/piyo");
+    comps.next();
+    assert_eq!(comps.as_path(), "foo/./bar/piyo");
+    comps.next();
+    assert_eq!(comps.as_path(), "bar/piyo");
+    comps.next_back();
+    assert_eq!(comps.as_path(), "bar");
+    comps.next_back();
+    assert_eq!(comps.as_path(), "");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn components_same_as_std() {
+    for (input, _, _) in COMPONENTS_TESTS {
+        let is_windows_specific =
+            input.contains(':') || input.contains('\\') || input.starts_with("//");
+        if cfg!(target_os = "windows") || is_windows_specific {
+            continue;
+        }
+        let actual = SlashPath::new(input)
+            .unwrap()
+            .components()
+            .map(SlashComponent::as_str)
+            .collect::<Vec<_>>();
+        let expected = Path::new(input)
+            .components()
+            .map(|c| c.as_os_str().to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn components_debug() {
+    let comps = SlashPath::new("/foo/bar").unwrap().components();
+    assert_eq!(
+        format!("{:?}", comps),
+        r#"[RootDir, Normal("foo"), Normal("bar")]"#,
+    );
+}
+
+// Test cases are from Go's path.Clean tests
+const CLEAN_TESTS: &[(&str, &str)] = &[
+    // Already clean
+    ("", "."),
+    ("abc", "abc"),
+    ("abc/def", "abc/def"),
+    ("a/b/c", "a/b/c"),
+    (".", "."),
+    ("..", ".."),
+    ("../..", "../.."),
+    ("../../abc", "../../abc"),
+    ("/abc", "/abc"),
+    ("/", "/"),
+    // Remove trailing slash
+    ("abc/", "abc"),
+    ("abc/def/", "abc/def"),
+    ("a/b/c/", "a/b/c"),
+    ("./", "."),
+    ("../", ".."),
+    ("../../", "../.."),
+    ("/abc/", "/abc"),
+    // Remove doubled slash
+    ("abc//def//ghi", "abc/def/ghi"),
+    ("//abc", "/abc"),
+    ("///abc", "/abc"),
+    ("//abc//", "/abc"),
+    ("abc//", "abc"),
+    // Remove . elements
+    ("abc/./def", "abc/def"),
+    ("/./abc/def", "/abc/def"),
+    ("abc/.", "abc"),
+    // Remove .. elements
+    ("abc/def/ghi/../jkl", "abc/def/jkl"),
+    ("abc/def/../ghi/../jkl", "abc/jkl"),
+    ("abc/def/..", "abc"),
+    ("abc/def/../..", "."),
+    ("/abc/def/../..", "/"),
+    ("abc/def/../../..", ".."),
+    ("/abc/def/../../..", "/"),
+    ("abc/def/../../../ghi/jkl/../../../mno", "../../mno"),
+    // Combinations
+    ("abc/./../def", "def"),
+    ("abc//./../def", "def"),
+    ("abc/../../././../def", "../../def"),
+    // Windows path prefixes
+    ("C:", "C:."),
+    ("C:/", "C:/"),
+    ("C:abc/..", "C:."),
+    ("C:../abc", "C:../abc"),
+    ("C:/../abc", "C:/abc"),
+    ("C:/abc/./def/../ghi/", "C:/abc/ghi"),
+    (r"\\server\share", r"\\server\share"),
+    (r"\\server\share/", r"\\server\share/"),
+    (r"\\server\share/abc/../../def", r"\\server\share/def"),
+    // `//` is not a UNC prefix
+    ("//server/share/abc/..", "/server/share"),
+    ("//foo/bar/../baz", "/foo/baz"),
+    ("//a/b/c/..", "/a/b"),
+    ("//a/b/../..", "/"),
+    ("//a/b/", "/a/b"),
+    ("//", "/"),
+    // Verbatim paths are not normalized
+    (r"\\?\C:/abc/../def", r"\\?\C:/abc/../def"),
+    (r"\\?\C:/abc/./def//", r"\\?\C:/abc/./def//"),
+    (
+        r"\\?\UNC\server\share/abc/..",
+        r"\\?\UNC\server\share/abc/..",
+    ),
+    (r"\\?\foo/../bar", r"\\?\foo/../bar"),
+];
+
+#[test]
+fn clean() {
+    for (input, expected) in CLEAN_TESTS {
+        assert_eq!(
+            SlashPath::new(input).unwrap().clean(),
+            *expected,
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn clean_is_idempotent() {
+    for (_, expected) in CLEAN_TESTS {
+        assert_eq!(
+            SlashPath::new(expected).unwrap().clean(),
+            *expected,
+            "{:?}",
+            expected
+        );
+    }
+}
+
+#[test]
+fn push() {
+    for (base, path, expected) in &[
+        ("", "", ""),
+        ("", "foo", "foo"),
+        ("foo", "", "foo/"),
+        ("foo", "bar", "foo/bar"),
+        ("foo/", "bar", "foo/bar"),
+        ("foo//", "bar", "foo//bar"),
+        ("foo/.", "bar", "foo/./bar"),
+        ("foo/..", "bar", "foo/../bar"),
+        ("/", "foo", "/foo"),
+        ("foo", "/bar", "/bar"),
+        ("/foo", "/bar", "/bar"),
+        ("C:", "foo", "C:foo"),
+        ("C:/", "foo", "C:/foo"),
+        ("C:/foo", "bar", "C:/foo/bar"),
+        ("C:/foo", "/bar", "C:/bar"),
+        ("C:foo", "/bar", "C:/bar"),
+        ("C:/foo", "D:/bar", "D:/bar"),
+        ("C:/foo", "D:bar", "D:bar"),
+        ("foo", "C:/bar", "C:/bar"),
+        (r"\\server\share", "foo", r"\\server\share/foo"),
+        (r"\\server\share/foo", "/bar", r"\\server\share/bar"),
+        ("/foo", r"\\server\share/bar", r"\\server\share/bar"),
+    ] {
+        let mut p = SlashPathBuf::from(*base);
+        p.push(path);
+        assert_eq!(p, *expected, "{:?}", (base, path));
+        assert_eq!(
+            SlashPath::new(base).unwrap().join(path),
+            *expected,
+            "{:?}",
+            (base, path)
+        );
+    }
+}
+
+#[test]
+fn parent_and_pop() {
+    for (input, expected) in &[
+        ("", None),
+        ("/", None),
+        ("foo", Some("")),
+        ("foo/", Some("")),
+        ("/foo", Some("/")),
+        ("/foo/bar", Some("/foo")),
+        ("/foo/bar/", Some("/foo")),
+        ("foo/./bar/.", Some("foo")),
+        ("foo//bar", Some("foo")),
+        ("foo/..", Some("foo")),
+        ("./foo", Some(".")),
+        (".", Some("")),
+        ("C:", None),
+        ("C:/", None),
+        ("C:foo", Some("C:")),
+        ("C:/foo", Some("C:/")),
+        (r"\\server\share", None),
+        (r"\\server\share/foo", Some(r"\\server\share/")),
+    ] {
+        let p = SlashPath::new(input).unwrap();
+        assert_eq!(p.parent().map(SlashPath::as_str), *expected, "{:?}", input);
+
+        let mut buf = p.to_slash_path_buf();
+        assert_eq!(buf.pop(), expected.is_some(), "{:?}", input);
+        assert_eq!(buf, expected.unwrap_or(input), "{:?}", input);
+    }
+}
+
+#[test]
+fn file_name_stem_extension() {
+    for (input, name, stem, ext) in &[
+        ("", None, None, None),
+        ("/", None, None, None),
+        ("foo", Some("foo"), Some("foo"), None),
+        ("foo.txt", Some("foo.txt"), Some("foo"), Some("txt")),
+        (
+            "foo/bar.tar.gz",
+            Some("bar.tar.gz"),
+            Some("bar.tar"),
+            Some("gz"),
+        ),
+        ("foo/bar.txt/", Some("bar.txt"), Some("bar"), Some("txt")),
+        ("foo/bar.txt/.", Some("bar.txt"), Some("bar"), Some("txt")),
+        ("foo/.bashrc", Some(".bashrc"), Some(".bashrc"), None),
+        (
+            "foo/.bashrc.bak",
+            Some(".bashrc.bak"),
+            Some(".bashrc"),
+            Some("bak"),
+        ),
+        ("foo/bar.", Some("bar."), Some("bar"), Some("")),
+        ("foo/..", None, None, None),
+        (".", None, None, None),
+        ("C:", None, None, None),
+        ("C:foo.rs", Some("foo.rs"), Some("foo"), Some("rs")),
+        (r"\\server\share", None, None, None),
+    ] {
+        let p = SlashPath::new(input).unwrap();
+        assert_eq!(p.file_name(), *name, "{:?}", input);
+        assert_eq!(p.file_stem(), *stem, "{:?}", input);
+        assert_eq!(p.extension(), *ext, "{:?}", input);
+    }
+}
+
+#[test]
+fn set_file_name() {
+    for (input, name, expected) in &[
+        ("", "foo", "foo"),
+        ("foo", "bar", "bar"),
+        ("foo/bar", "piyo", "foo/piyo"),
+        ("foo/bar/", "piyo", "foo/piyo"),
+        ("/", "foo", "/foo"),
+        ("foo/..", "bar", "foo/../bar"),
+        ("C:", "foo", "C:foo"),
+        ("C:/foo", "bar", "C:/bar"),
+    ] {
+        let mut p = SlashPathBuf::from(*input);
+        p.set_file_name(name);
+        assert_eq!(p, *expected, "{:?}", (input, name));
+        assert_eq!(
+            SlashPath::new(input).unwrap().with_file_name(name),
+            *expected
+        );
+    }
+}
+
+#[test]
+fn set_extension() {
+    for (input, ext, expected, updated) in &[
+        ("", "rs", "", false),
+        ("/", "rs", "/", false),
+        ("foo", "rs", "foo.rs", true),
+        ("foo.txt", "rs", "foo.rs", true),
+        ("foo.txt", "", "foo", true),
+        ("foo/bar.tar.gz", "xz", "foo/bar.tar.xz", true),
+        ("foo/bar.txt/", "rs", "foo/bar.rs", true),
+        ("foo/.bashrc", "bak", "foo/.bashrc.bak", true),
+        ("foo/..", "rs", "foo/..", false),
+        ("C:/foo.txt", "rs", "C:/foo.rs", true),
+    ] {
+        let mut p = SlashPathBuf::from(*input);
+        assert_eq!(p.set_extension(ext), *updated, "{:?}", (input, ext));
+        assert_eq!(p, *expected, "{:?}", (input, ext));
+        assert_eq!(
+            SlashPath::new(input).unwrap().with_extension(ext),
+            *expected
+        );
+    }
+}
+
+#[test]
+fn relative_to() {
+    for (path, base, expected) in &[
+        ("", "", Some(".")),
+        ("foo", "foo", Some(".")),
+        ("foo/bar", "foo", Some("bar")),
+        ("foo", "foo/bar", Some("..")),
+        ("foo/bar/piyo", "foo/x/y", Some("../../bar/piyo")),
+        ("foo/./bar/../piyo", "foo//x/", Some("../piyo")),
+        ("foo", "bar", Some("../foo")),
+        ("foo", ".", Some("foo")),
+        (".", "foo", Some("..")),
+        ("../foo", "bar", Some("../../foo")),
+        ("../foo", "../bar", Some("../foo")),
+        ("foo", "..", None),
+        ("foo", "../bar", None),
+        ("/", "/", Some(".")),
+        (
+            "/foo/src/lib.rs",
+            "/foo/target/debug",
+            Some("../../src/lib.rs"),
+        ),
+        ("/foo", "/", Some("foo")),
+        ("/", "/foo", Some("..")),
+        ("/../foo", "/bar/..", Some("foo")),
+        ("/foo", "foo", None),
+        ("foo", "/foo", None),
+        ("C:/foo/bar", "C:/foo", Some("bar")),
+        ("C:/foo/bar", "c:/foo/piyo", Some("../bar")),
+        ("C:foo", "C:bar", Some("../foo")),
+        ("C:/foo", "D:/foo", None),
+        ("C:/foo", "C:foo", None),
+        ("C:/foo", "/foo", None),
+        ("/foo", "C:/foo", None),
+        (r"\\server\share/foo", r"\\SERVER\share/bar", Some("../foo")),
+        (r"\\server\share/foo", r"\\server\other/foo", None),
+    ] {
+        assert_eq!(
+            SlashPath::new(path).unwrap().relative_to(base),
+            expected.map(SlashPathBuf::from),
+            "{:?}",
+            (path, base),
+        );
+    }
+}
+
+#[test]
+fn absolute_and_root() {
+    // (input, is_absolute_posix, is_absolute_windows, has_root, is_drive_relative)
+    for (input, posix, windows, root, drive_rel) in &[
+        ("", false, false, false, false),
+        ("foo", false, false, false, false),
+        ("./foo", false, false, false, false),
+        ("../foo", false, false, false, false),
+        ("/", true, false, true, false),
+        ("/foo", true, false, true, false),
+        ("//foo", true, false, true, false),
+        ("C:", false, false, false, true),
+        ("c:foo", false, false, false, true),
+        ("C:./foo", false, false, false, true),
+        ("C:/", false, true, true, false),
+        ("C:/foo", false, true, true, false),
+        ("1:/foo", false, false, false, false),
+        (r"\\server\share", false, true, true, false),
+        (r"\\server\share/foo", false, true, true, false),
+        ("//server/share/foo", true, true, true, false),
+        (r"\\?\C:/foo", false, true, true, false),
+        (r"\\?\UNC\server\share/foo", false, true, true, false),
+        (r"\\.\COM42/foo", false, true, true, false),
+    ] {
+        let p = SlashPath::new(input).unwrap();
+        assert_eq!(p.is_absolute_posix(), *posix, "{:?}", input);
+        assert_eq!(p.is_absolute_windows(), *windows, "{:?}", input);
+        assert_eq!(p.has_root(), *root, "{:?}", input);
+        assert_eq!(p.is_drive_relative(), *drive_rel, "{:?}", input);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn absolute_same_as_std() {
+    for input in &[
+        "",
+        "foo",
+        "/foo",
+        "C:",
+        "C:foo",
+        "C:/foo",
+        r"\\server\share/foo",
+    ] {
+        let p = SlashPath::new(input).unwrap();
+        let path = p.to_path_buf();
+        #[cfg(target_os = "windows")]
+        assert_eq!(p.is_absolute_windows(), path.is_absolute(), "{:?}", input);
+        #[cfg(not(target_os = "windows"))]
+        assert_eq!(p.is_absolute_posix(), path.is_absolute(), "{:?}", input);
+        #[cfg(target_os = "windows")]
+        assert_eq!(p.has_root(), path.has_root(), "{:?}", input);
+    }
+}
@@ -1,6 +1,6 @@
-#![cfg(not(target_os = "windows"))]
+#![cfg(all(feature = "std", not(target_os = "windows")))]
 
-use path_slash::{CowExt as _, PathBufExt as _, PathExt as _};
+use path_slash::{CowExt as _, NonUtf8, PathBufExt as _, PathExt as _};
 use std::borrow::Cow;
 use std::ffi::OsStr;
 use std::os::unix::ffi::OsStrExt;
@@ -42,6 +42,29 @@ fn invalid_utf8_to_slash_lossy() {
     }
 }
 
+#[test]
+fn invalid_utf8_write_slash() {
+    for (input, output) in INVALID_UTF8_TO_SLASH {
+        let p = Path::new(OsStr::from_bytes(input));
+
+        let mut buf = String::from("foo");
+        assert_eq!(p.write_slash(&mut buf), Err(NonUtf8), "{:x?}", input);
+        assert_eq!(buf, "foo");
+
+        buf.clear();
+        p.write_slash_lossy(&mut buf);
+        assert_eq!(buf, *output, "{:x?}", input);
+    }
+}
+
+#[test]
+fn invalid_utf8_display_slash() {
+    for (input, output) in INVALID_UTF8_TO_SLASH {
+        let p = Path::new(OsStr::from_bytes(input));
+        assert_eq!(p.display_slash().to_string(), *output, "{:x?}", input);
+    }
+}
+
 #[test]
 fn invalid_utf8_to_slash() {
     for (input, _) in INVALID_UTF8_TO_SLASH {
@@ -49,3 +72,30 @@ fn invalid_utf8_to_slash() {
         assert_eq!(p.to_slash(), None, "{:x?}", input);
     }
 }
+
+const SLASH_BYTES: &[&[u8]] = &[
+    b"",
+    b"/",
+    b"foo/bar/piyo.txt",
+    b"aaa/\xc3",
+    b"aaa/\xc3/",
+    b"/\xff\xfe/\x80.txt",
+    b"foo\\\xc3",
+];
+
+#[test]
+fn invalid_utf8_to_slash_bytes() {
+    for b in SLASH_BYTES {
+        let p = Path::new(OsStr::from_bytes(b));
+        assert_eq!(p.to_slash_bytes(), Cow::Borrowed(*b), "{:x?}", b);
+    }
+}
+
+#[test]
+fn invalid_utf8_from_slash_bytes() {
+    for b in SLASH_BYTES {
+        let p = PathBuf::from_slash_bytes(b);
+        assert_eq!(p.as_os_str().as_bytes(), *b, "{:x?}", b);
+        assert_eq!(p.to_slash_bytes(), *b, "{:x?}", b);
+    }
+}
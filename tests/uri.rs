@@ -0,0 +1,293 @@
+use path_slash::uri::{self, FileUriError};
+use path_slash::PathStyle;
+#[cfg(feature = "std")]
+use path_slash::{PathBufExt as _, PathExt as _};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+#[test]
+fn to_file_uri_posix() {
+    for (path, expected) in &[
+        ("/", "file:///"),
+        ("/foo/bar.txt", "file:///foo/bar.txt"),
+        ("/foo bar/a#b?c%d", "file:///foo%20bar/a%23b%3Fc%25d"),
+        ("/a:b@c!$&'()*+,;=-._~", "file:///a:b@c!$&'()*+,;=-._~"),
+        ("/あ", "file:///%E3%81%82"),
+        (r"/a\b", "file:///a%5Cb"),
+        ("/foo//bar/./baz/", "file:///foo/bar/baz/"),
+    ] {
+        assert_eq!(
+            uri::to_file_uri(path, PathStyle::Posix),
+            Ok(expected.to_string()),
+            "{:?}",
+            path
+        );
+    }
+    for path in &["", "foo", "./foo", "C:/foo"] {
+        assert_eq!(
+            uri::to_file_uri(path, PathStyle::Posix),
+            Err(FileUriError::NotAbsolute),
+            "{:?}",
+            path
+        );
+    }
+}
+
+#[test]
+fn to_file_uri_windows() {
+    for (path, expected) in &[
+        ("C:/", "file:///C:/"),
+        ("c:/foo/bar.txt", "file:///C:/foo/bar.txt"),
+        (r"C:\x\y z", "file:///C:/x/y%20z"),
+        (r"\\?\C:\foo", "file:///C:/foo"),
+        (r"\\server\share", "file://server/share"),
+        (r"\\server\share\foo bar", "file://server/share/foo%20bar"),
+        (r"\\server\share/foo", "file://server/share/foo"),
+        (r"\\?\UNC\server\share\foo", "file://server/share/foo"),
+        ("C:/foo//bar/./baz/", "file:///C:/foo/bar/baz/"),
+        (r"C:\foo\\bar\.\baz", "file:///C:/foo/bar/baz"),
+        (r"\\?\C:\foo\.", "file:///C:/foo/."),
+    ] {
+        assert_eq!(
+            uri::to_file_uri(path, PathStyle::Windows),
+            Ok(expected.to_string()),
+            "{:?}",
+            path
+        );
+    }
+    for (path, err) in &[
+        ("", FileUriError::NotAbsolute),
+        ("foo", FileUriError::NotAbsolute),
+        ("/foo", FileUriError::NotAbsolute),
+        ("C:", FileUriError::NotAbsolute),
+        ("C:foo", FileUriError::NotAbsolute),
+        (r"\\?\pictures\foo", FileUriError::UnsupportedPrefix),
+        (r"\\.\COM1", FileUriError::UnsupportedPrefix),
+    ] {
+        assert_eq!(
+            uri::to_file_uri(path, PathStyle::Windows),
+            Err(err.clone()),
+            "{:?}",
+            path
+        );
+    }
+}
+
+#[test]
+fn from_file_uri_posix() {
+    for (input, expected) in &[
+        ("file:///", "/"),
+        ("file:///foo/bar.txt", "/foo/bar.txt"),
+        ("FILE:///foo", "/foo"),
+        ("file:/foo", "/foo"),
+        ("file://localhost/foo", "/foo"),
+        ("file://LocalHost/foo", "/foo"),
+        ("file:///foo%20bar/a%23b%3fc%25d", "/foo bar/a#b?c%d"),
+        ("file:///%E3%81%82", "/あ"),
+        ("file:///foo?query#fragment", "/foo"),
+        ("file:///foo#a/b", "/foo"),
+        ("file:///C:/foo", "/C:/foo"),
+    ] {
+        assert_eq!(
+            uri::from_file_uri(input, PathStyle::Posix).unwrap(),
+            *expected,
+            "{:?}",
+            input
+        );
+    }
+    for (input, err) in &[
+        ("", FileUriError::MissingScheme),
+        ("/foo", FileUriError::MissingScheme),
+        (":/foo", FileUriError::MissingScheme),
+        ("1file:///foo", FileUriError::MissingScheme),
+        (
+            "https://example.com/foo",
+            FileUriError::UnsupportedScheme("https".to_string()),
+        ),
+        (
+            "untitled:Untitled-1",
+            FileUriError::UnsupportedScheme("untitled".to_string()),
+        ),
+        (
+            "file://server/share/foo",
+            FileUriError::UnsupportedHost("server".to_string()),
+        ),
+        ("file:foo", FileUriError::NotAbsolute),
+        ("file:///foo%2", FileUriError::InvalidPercentEncoding),
+        ("file:///foo%zz", FileUriError::InvalidPercentEncoding),
+        ("file:///foo%ff", FileUriError::NonUnicode),
+        ("file:///a%2Fb", FileUriError::DisallowedEncodedChar('/')),
+        ("file:///a%2f..", FileUriError::DisallowedEncodedChar('/')),
+        ("file:///a%00b", FileUriError::DisallowedEncodedChar('\0')),
+        ("file:///a%5Cb", FileUriError::DisallowedEncodedChar('\\')),
+    ] {
+        assert_eq!(
+            uri::from_file_uri(input, PathStyle::Posix),
+            Err(err.clone()),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn from_file_uri_windows() {
+    for (input, expected) in &[
+        ("file:///C:/", "C:/"),
+        ("file:///C:", "C:/"),
+        ("file:///c:/foo/bar.txt", "C:/foo/bar.txt"),
+        ("file:///C:/x/y%20z", "C:/x/y z"),
+        ("file:///c%3A/foo", "C:/foo"),
+        ("file:///C|/foo", "C:/foo"),
+        ("file:/C:/foo", "C:/foo"),
+        ("file://localhost/C:/foo", "C:/foo"),
+        ("file://server/share", r"\\server\share"),
+        ("file://server/share/foo%20bar", r"\\server\share/foo bar"),
+    ] {
+        assert_eq!(
+            uri::from_file_uri(input, PathStyle::Windows).unwrap(),
+            *expected,
+            "{:?}",
+            input
+        );
+    }
+    for (input, err) in &[
+        ("file:///foo", FileUriError::NotAbsolute),
+        ("file:///", FileUriError::NotAbsolute),
+        ("file:///C:foo", FileUriError::NotAbsolute),
+        ("file:///CD:/foo", FileUriError::NotAbsolute),
+        (
+            "file://server",
+            FileUriError::UnsupportedHost("server".to_string()),
+        ),
+        (
+            "file://server/",
+            FileUriError::UnsupportedHost("server".to_string()),
+        ),
+        (
+            "file:///C:/a%5Cb",
+            FileUriError::DisallowedEncodedChar('\\'),
+        ),
+        ("file:///C:/a%2Fb", FileUriError::DisallowedEncodedChar('/')),
+        (
+            "file:///C:/a%00b",
+            FileUriError::DisallowedEncodedChar('\0'),
+        ),
+    ] {
+        assert_eq!(
+            uri::from_file_uri(input, PathStyle::Windows),
+            Err(err.clone()),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn roundtrip() {
+    for (path, style) in &[
+        ("/foo bar/%/#/?/あ", PathStyle::Posix),
+        ("C:/foo bar/%/#/あ", PathStyle::Windows),
+        (r"\\server\share/foo bar/%/#/あ", PathStyle::Windows),
+    ] {
+        let uri = uri::to_file_uri(path, *style).unwrap();
+        assert_eq!(
+            uri::from_file_uri(&uri, *style).unwrap(),
+            *path,
+            "{:?}",
+            uri
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn path_to_file_uri() {
+    #[cfg(target_os = "windows")]
+    let (path, expected) = (r"C:\foo\bar baz.txt", "file:///C:/foo/bar%20baz.txt");
+    #[cfg(not(target_os = "windows"))]
+    let (path, expected) = ("/foo/bar baz.txt", "file:///foo/bar%20baz.txt");
+
+    assert_eq!(Path::new(path).to_file_uri().unwrap(), expected);
+    assert_eq!(
+        PathBuf::from_file_uri(expected).unwrap(),
+        PathBuf::from(path)
+    );
+    assert_eq!(
+        Path::new("foo").to_file_uri(),
+        Err(FileUriError::NotAbsolute)
+    );
+
+    #[cfg(target_os = "windows")]
+    let roots = &[
+        ("C:/", "file:///C:/"),
+        (r"C:\", "file:///C:/"),
+        (r"C:\foo\", "file:///C:/foo/"),
+        (r"\\server\share\foo", "file://server/share/foo"),
+    ];
+    #[cfg(not(target_os = "windows"))]
+    let roots = &[("/", "file:///"), ("/foo/", "file:///foo/")];
+    for (path, expected) in roots {
+        assert_eq!(
+            Path::new(path).to_file_uri().unwrap(),
+            *expected,
+            "{:?}",
+            path
+        );
+    }
+
+    let cwd = std::env::current_dir().unwrap();
+    let uri = cwd.to_file_uri().unwrap();
+    assert!(uri.starts_with("file://"), "{:?}", uri);
+    assert_eq!(PathBuf::from_file_uri(&uri).unwrap(), cwd);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn path_to_file_uri_same_as_uri_module() {
+    #[cfg(target_os = "windows")]
+    let paths = &[
+        "C:/",
+        r"C:\",
+        "C:/foo",
+        r"C:\foo\",
+        "C:/foo/",
+        r"C:\foo\\bar\.\baz",
+        "C://foo//./bar/",
+        r"C:\foo\..\bar",
+        r"\\?\C:\foo\.\bar",
+        r"\\?\C:\foo\",
+        r"\\?\UNC\server\share\foo",
+        r"\\server\share",
+        r"\\server\share\",
+        r"\\server\share/foo bar/",
+        "//server/share/foo",
+        "foo",
+        "/foo",
+        "C:foo",
+        r"\\.\COM1",
+    ];
+    #[cfg(not(target_os = "windows"))]
+    let paths = &[
+        "/",
+        "/foo",
+        "/foo/",
+        "//foo",
+        "/foo//bar/./baz/",
+        "/./foo/.",
+        "/foo/../bar",
+        r"/a\b",
+        "/foo bar/a#b",
+        "foo",
+        "./foo",
+        "",
+    ];
+    for path in paths {
+        assert_eq!(
+            Path::new(path).to_file_uri(),
+            uri::to_file_uri(path, PathStyle::native()),
+            "{:?}",
+            path
+        );
+    }
+}
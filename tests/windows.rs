@@ -1,6 +1,6 @@
-#![cfg(target_os = "windows")]
+#![cfg(all(feature = "std", target_os = "windows"))]
 
-use path_slash::{CowExt as _, PathBufExt as _, PathExt as _};
+use path_slash::{CowExt as _, NonUtf8, PathBufExt as _, PathExt as _};
 use std::borrow::Cow;
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
@@ -206,6 +206,56 @@ fn invalid_utf16_seq_to_slash_lossy() {
     }
 }
 
+#[test]
+fn invalid_utf16_seq_write_slash() {
+    for (b, s) in INVALID_UTF16_TO_SLASH {
+        let o = OsString::from_wide(b);
+        let p = Path::new(&o);
+
+        let mut buf = String::from("foo");
+        assert_eq!(p.write_slash(&mut buf), Err(NonUtf8), "{:x?}", b);
+        assert_eq!(buf, "foo");
+
+        buf.clear();
+        p.write_slash_lossy(&mut buf);
+        assert_eq!(buf, *s, "{:x?}", b);
+    }
+}
+
+#[test]
+fn invalid_utf16_seq_display_slash() {
+    for (b, s) in INVALID_UTF16_TO_SLASH {
+        let o = OsString::from_wide(b);
+        let p = Path::new(&o);
+        assert_eq!(p.display_slash().to_string(), *s, "{:x?}", b);
+    }
+}
+
+#[test]
+fn display_slash_with_prefix() {
+    for input in &[
+        r"\",
+        r"C:",
+        r"C:\",
+        r"C:foo",
+        r"C:.\foo",
+        r"C:\foo\bar",
+        r"C:\foo\bar\",
+        r"\\?\C:\foo\bar",
+        r"\\server\share",
+        r"\\server\share\foo\",
+        r"\\?\UNC\server\share\foo",
+    ] {
+        let p = Path::new(input);
+        assert_eq!(
+            p.display_slash().to_string(),
+            p.to_slash_lossy(),
+            "{:?}",
+            input
+        );
+    }
+}
+
 #[test]
 fn invalid_utf16_seq_to_slash() {
     for (b, _) in INVALID_UTF16_TO_SLASH {
@@ -243,3 +293,123 @@ fn invalid_utf16_seq_cow_from_slash() {
         assert_eq!(p.to_str().unwrap(), *s, "{:x?}", b);
     }
 }
+
+#[test]
+fn same_as_windows_module() {
+    for input in &[
+        r"foo\bar\piyo.txt",
+        r"foo\.\bar\..\",
+        r"C:",
+        r"C:foo",
+        r"C:\foo\bar",
+        r"\\?\C:\foo\bar",
+        r"\\?\C:\foo\.\bar",
+        r"\\server\share",
+        r"\\server\share\foo\bar",
+        r"\\?\UNC\server\share\foo",
+        r"\\.\COM42\foo",
+    ] {
+        let slash = path_slash::windows::to_slash(input);
+        assert_eq!(
+            Path::new(input).to_slash(),
+            Some(slash.clone()),
+            "{:?}",
+            input
+        );
+        assert_eq!(
+            PathBuf::from_slash(&slash).as_os_str(),
+            std::ffi::OsStr::new(&*path_slash::windows::from_slash(&slash)),
+            "{:?}",
+            input,
+        );
+    }
+}
+
+#[test]
+fn invalid_utf16_seq_to_slash_wide() {
+    for (b, _) in INVALID_UTF16_TO_SLASH {
+        let o = OsString::from_wide(b);
+        let p = Path::new(&o);
+        let expected = b
+            .iter()
+            .map(|&u| if u == b'\\' as u16 { b'/' as u16 } else { u })
+            .collect::<Vec<_>>();
+        assert_eq!(p.to_slash_wide(), expected, "{:x?}", b);
+        assert_eq!(
+            p.to_slash_wtf8(),
+            path_slash::windows::wide_to_wtf8(&expected),
+            "{:x?}",
+            b
+        );
+    }
+}
+
+#[test]
+fn invalid_utf16_seq_from_slash_wide() {
+    for (b, _) in INVALID_UTF16_FROM_SLASH {
+        let expected = b
+            .iter()
+            .map(|&u| if u == b'/' as u16 { b'\\' as u16 } else { u })
+            .collect::<Vec<_>>();
+        let expected = PathBuf::from(OsString::from_wide(&expected));
+        assert_eq!(PathBuf::from_slash_wide(b), expected, "{:x?}", b);
+
+        let wtf8 = path_slash::windows::wide_to_wtf8(b);
+        assert_eq!(PathBuf::from_slash_wtf8(&wtf8), Some(expected), "{:x?}", b);
+    }
+}
+
+#[test]
+fn invalid_utf16_seq_slash_wide_roundtrip() {
+    for (b, _) in INVALID_UTF16_TO_SLASH {
+        let o = OsString::from_wide(b);
+        let p = Path::new(&o);
+        assert_eq!(PathBuf::from_slash_wide(p.to_slash_wide()), p, "{:x?}", b);
+        assert_eq!(
+            PathBuf::from_slash_wtf8(p.to_slash_wtf8()).unwrap(),
+            p,
+            "{:x?}",
+            b
+        );
+    }
+}
+
+#[test]
+fn same_prefix_as_std() {
+    use path_slash::windows::{self, Prefix};
+    use std::path::{Component, Prefix as StdPrefix};
+
+    for input in &[
+        "foo",
+        r"\foo",
+        "C:",
+        "c:foo",
+        r"C:.\foo",
+        r"C:\foo",
+        r"\\server\share",
+        r"\\server\share\foo",
+        r"\\?\C:\foo",
+        r"\\?\pictures\foo",
+        r"\\?\UNC\server\share\foo",
+        r"\\.\COM42\foo",
+    ] {
+        let std_prefix = match Path::new(input).components().next() {
+            Some(Component::Prefix(p)) => Some(p.kind()),
+            _ => None,
+        };
+        let prefix = windows::parse_prefix(input);
+        let same = match (prefix, std_prefix) {
+            (None, None) => true,
+            (Some(Prefix::Disk(a)), Some(StdPrefix::Disk(b))) => a == b,
+            (Some(Prefix::VerbatimDisk(a)), Some(StdPrefix::VerbatimDisk(b))) => a == b,
+            (Some(Prefix::Verbatim(a)), Some(StdPrefix::Verbatim(b))) => a == b,
+            (Some(Prefix::DeviceNS(a)), Some(StdPrefix::DeviceNS(b))) => a == b,
+            (Some(Prefix::UNC(a, b)), Some(StdPrefix::UNC(c, d))) => a == c && b == d,
+            (Some(Prefix::VerbatimUNC(a, b)), Some(StdPrefix::VerbatimUNC(c, d))) => {
+                a == c && b == d
+            }
+            _ => false,
+        };
+        assert!(same, "{:?} vs {:?} for {:?}", prefix, std_prefix, input);
+    }
+}
@@ -0,0 +1,315 @@
+use path_slash::windows::{self, Prefix};
+use std::borrow::Cow;
+
+const TO_SLASH_TESTS: &[(&str, &str)] = &[
+    ("", ""),
+    (r"\", "/"),
+    (r"foo", "foo"),
+    (r"\foo", "/foo"),
+    (r"foo\", "foo/"),
+    (r"\foo\", "/foo/"),
+    (r".\foo", "./foo"),
+    (r"..\foo", "../foo"),
+    (r"foo\..", "foo/.."),
+    (r"foo\bar", "foo/bar"),
+    (r"foo\\bar", "foo/bar"),
+    (r"foo\.\bar", "foo/bar"),
+    (r"foo\..\bar", "foo/../bar"),
+    (r"foo/bar\piyo", "foo/bar/piyo"),
+    (r"あ\い\う\え\お\", "あ/い/う/え/お/"),
+    (r"C:", "C:"),
+    (r"C:foo", "C:foo"),
+    (r"C:.\foo", "C:foo"),
+    (r"C:.", "C:"),
+    (r"C:\foo\bar", "C:/foo/bar"),
+    (r"c:\foo\bar", "c:/foo/bar"),
+    (r"C:/foo/bar", "C:/foo/bar"),
+    (r"\\?\C:\foo\bar", r"\\?\C:/foo/bar"),
+    (r"\\?\C:\foo\.\bar", r"\\?\C:/foo/./bar"),
+    (r"\\?\C:\foo/bar", r"\\?\C:/foo/bar"),
+    (r"\\server\share", r"\\server\share"),
+    (r"\\server\share\foo\bar", r"\\server\share/foo/bar"),
+    (r"\\?\UNC\server\share\foo", r"\\?\UNC\server\share/foo"),
+    (r"\\.\COM42\foo", r"\\.\COM42/foo"),
+    (r"\\server", "/server"),
+];
+
+#[test]
+fn to_slash() {
+    for (input, expected) in TO_SLASH_TESTS {
+        assert_eq!(windows::to_slash(input), *expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn to_slash_borrowed() {
+    for input in &["", "foo", "foo/bar", "C:/foo/bar", "/あ/い/う"] {
+        match windows::to_slash(input) {
+            Cow::Borrowed(s) => assert_eq!(s, *input),
+            Cow::Owned(s) => panic!("{:?} was converted to {:?}", input, s),
+        }
+    }
+}
+
+const FROM_SLASH_TESTS: &[(&str, &str)] = &[
+    ("", ""),
+    ("/", r"\"),
+    ("foo", "foo"),
+    ("/foo/", r"\foo\"),
+    ("foo//bar", r"foo\\bar"),
+    ("foo/../bar", r"foo\..\bar"),
+    ("C:/foo/bar", r"C:\foo\bar"),
+    (r"\\?\C:/foo/bar", r"\\?\C:\foo\bar"),
+    (r"\\server\share/foo/bar", r"\\server\share\foo\bar"),
+    ("/あ/い/う/え/お/", r"\あ\い\う\え\お\"),
+];
+
+#[test]
+fn from_slash() {
+    for (input, expected) in FROM_SLASH_TESTS {
+        assert_eq!(windows::from_slash(input), *expected, "{:?}", input);
+    }
+}
+
+#[test]
+fn from_slash_to_slash() {
+    for (_, path) in TO_SLASH_TESTS {
+        let converted = windows::to_slash(path);
+        assert_eq!(
+            windows::to_slash(&windows::from_slash(&converted)),
+            converted,
+            "{:?}",
+            path,
+        );
+    }
+}
+
+fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().collect()
+}
+
+#[test]
+fn wtf8_wide_conversion() {
+    for s in &["", "foo", "a/b", "あいう", "🐶/😺", "\u{FFFD}"] {
+        let w = wide(s);
+        assert_eq!(windows::wide_to_wtf8(&w), s.as_bytes(), "{:?}", s);
+        assert_eq!(windows::wtf8_to_wide(s.as_bytes()), Some(w), "{:?}", s);
+    }
+
+    for (w, b) in &[
+        (&[0xd800][..], &b"\xed\xa0\x80"[..]),
+        (&[0xdfff], b"\xed\xbf\xbf"),
+        (&[0x61, 0xd800, 0x62], b"a\xed\xa0\x80b"),
+        (&[0xdc00, 0xd800], b"\xed\xb0\x80\xed\xa0\x80"),
+        (&[0xd83d, 0xd83d, 0xdc36], b"\xed\xa0\xbd\xf0\x9f\x90\xb6"),
+    ] {
+        assert_eq!(windows::wide_to_wtf8(w), *b, "{:x?}", w);
+        assert_eq!(windows::wtf8_to_wide(b), Some(w.to_vec()), "{:x?}", b);
+    }
+}
+
+#[test]
+fn invalid_wtf8() {
+    for b in &[
+        &b"\xff"[..],
+        b"\x80",
+        b"a\xc3",
+        b"\xe3\x81",
+        b"\xc0\xaf",                 // Overlong encoding
+        b"\xf4\x90\x80\x80",         // Out of range
+        b"\xed\xa0\xbd\xed\xb0\xb6", // Surrogate pair encoded separately
+    ] {
+        assert_eq!(windows::wtf8_to_wide(b), None, "{:x?}", b);
+    }
+}
+
+#[test]
+fn to_slash_wide_and_wtf8() {
+    for (input, expected) in TO_SLASH_TESTS {
+        assert_eq!(
+            windows::to_slash_wide(&wide(input)),
+            wide(expected),
+            "{:?}",
+            input,
+        );
+        assert_eq!(
+            windows::to_slash_wtf8(input.as_bytes()),
+            expected.as_bytes(),
+            "{:?}",
+            input,
+        );
+    }
+}
+
+#[test]
+fn from_slash_wide_and_wtf8() {
+    for (input, expected) in FROM_SLASH_TESTS {
+        assert_eq!(
+            windows::from_slash_wide(&wide(input)),
+            wide(expected),
+            "{:?}",
+            input,
+        );
+        assert_eq!(
+            windows::from_slash_wtf8(input.as_bytes()),
+            expected.as_bytes(),
+            "{:?}",
+            input,
+        );
+    }
+}
+
+#[test]
+fn unpaired_surrogate_roundtrip() {
+    let path = [
+        u16::from(b'C'),
+        u16::from(b':'),
+        u16::from(b'\\'),
+        0xd800,
+        u16::from(b'\\'),
+        u16::from(b'a'),
+        0xdc00,
+    ];
+    let slash = [
+        u16::from(b'C'),
+        u16::from(b':'),
+        u16::from(b'/'),
+        0xd800,
+        u16::from(b'/'),
+        u16::from(b'a'),
+        0xdc00,
+    ];
+    assert_eq!(windows::to_slash_wide(&path), slash);
+    assert_eq!(windows::from_slash_wide(&slash), path);
+
+    let wtf8 = windows::wide_to_wtf8(&path);
+    let slash_wtf8 = windows::to_slash_wtf8(&wtf8);
+    assert_eq!(&*slash_wtf8, &b"C:/\xed\xa0\x80/a\xed\xb0\x80"[..]);
+    assert_eq!(windows::from_slash_wtf8(&slash_wtf8), wtf8);
+    assert_eq!(windows::wtf8_to_wide(&slash_wtf8), Some(slash.to_vec()));
+}
+
+#[test]
+fn wtf8_borrowed() {
+    for input in &["", "foo", "foo/bar", "C:/foo/bar"] {
+        assert!(matches_borrowed(windows::to_slash_wtf8(input.as_bytes())));
+    }
+    for input in &["", "foo", r"foo\bar", r"C:\foo\bar"] {
+        assert!(matches_borrowed(windows::from_slash_wtf8(input.as_bytes())));
+    }
+}
+
+fn matches_borrowed(c: Cow<'_, [u8]>) -> bool {
+    match c {
+        Cow::Borrowed(_) => true,
+        Cow::Owned(_) => false,
+    }
+}
+
+const PREFIX_TESTS: &[(&str, Option<Prefix<'static>>, &str)] = &[
+    ("", None, ""),
+    ("foo", None, "foo"),
+    (r"\foo", None, r"\foo"),
+    ("/foo", None, "/foo"),
+    ("C", None, "C"),
+    ("1:", None, "1:"),
+    ("C:", Some(Prefix::Disk(b'C')), ""),
+    ("c:", Some(Prefix::Disk(b'C')), ""),
+    ("C:foo", Some(Prefix::Disk(b'C')), "foo"),
+    (r"C:\foo", Some(Prefix::Disk(b'C')), r"\foo"),
+    ("C:/foo", Some(Prefix::Disk(b'C')), "/foo"),
+    (r"\\server\share", Some(Prefix::UNC("server", "share")), ""),
+    (
+        r"\\server\share\foo",
+        Some(Prefix::UNC("server", "share")),
+        r"\foo",
+    ),
+    (
+        "//server/share/foo",
+        Some(Prefix::UNC("server", "share")),
+        "/foo",
+    ),
+    (
+        r"\\server\share/foo",
+        Some(Prefix::UNC("server", "share")),
+        "/foo",
+    ),
+    (r"\\server", None, r"\\server"),
+    (r"\\server\", None, r"\\server\"),
+    (r"\\\share", None, r"\\\share"),
+    (r"\\?\C:", Some(Prefix::VerbatimDisk(b'C')), ""),
+    (r"\\?\c:\foo", Some(Prefix::VerbatimDisk(b'C')), r"\foo"),
+    (r"\\?\C:/foo", Some(Prefix::VerbatimDisk(b'C')), "/foo"),
+    (r"\\?\C:foo", Some(Prefix::Verbatim("C:foo")), ""),
+    (
+        r"\\?\pictures\foo",
+        Some(Prefix::Verbatim("pictures")),
+        r"\foo",
+    ),
+    (
+        r"\\?\pictures/foo",
+        Some(Prefix::Verbatim("pictures")),
+        "/foo",
+    ),
+    (r"\\?\", Some(Prefix::Verbatim("")), ""),
+    (
+        r"\\?\UNC\server\share",
+        Some(Prefix::VerbatimUNC("server", "share")),
+        "",
+    ),
+    (
+        r"\\?\UNC\server\share\foo",
+        Some(Prefix::VerbatimUNC("server", "share")),
+        r"\foo",
+    ),
+    (
+        r"\\?\UNC\server\share/foo",
+        Some(Prefix::VerbatimUNC("server", "share")),
+        "/foo",
+    ),
+    (
+        r"\\?\UNC\server",
+        Some(Prefix::VerbatimUNC("server", "")),
+        "",
+    ),
+    (r"\\.\COM42", Some(Prefix::DeviceNS("COM42")), ""),
+    (r"\\.\COM42\foo", Some(Prefix::DeviceNS("COM42")), r"\foo"),
+    ("//./COM42/foo", Some(Prefix::DeviceNS("COM42")), "/foo"),
+    (r"\\.\あ\foo", Some(Prefix::DeviceNS("あ")), r"\foo"),
+];
+
+#[test]
+fn parse_prefix() {
+    for (input, prefix, rest) in PREFIX_TESTS {
+        assert_eq!(windows::parse_prefix(input), *prefix, "{:?}", input);
+        assert_eq!(
+            windows::split_prefix(input),
+            (*prefix, *rest),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn parse_prefix_of_slash_path() {
+    // The prefix is not changed by the conversion into a slash path
+    for (input, prefix, _) in PREFIX_TESTS {
+        let slash = windows::to_slash(input);
+        assert_eq!(windows::parse_prefix(&slash), *prefix, "{:?}", slash);
+    }
+}
+
+#[test]
+fn prefix_is_verbatim() {
+    for (input, prefix, _) in PREFIX_TESTS {
+        if let Some(prefix) = prefix {
+            assert_eq!(
+                prefix.is_verbatim(),
+                input.starts_with(r"\\?\"),
+                "{:?}",
+                input
+            );
+        }
+    }
+}